@@ -0,0 +1,92 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+// word lists tables can pick from as (name, file, alphabet), a card's letter is its index in
+// the alphabet. Adding one takes an entry here and a `WordList` variant, alphabets can't be
+// changed by a table after the contract is built
+const WORD_LISTS: &[(&str, &str, &str)] =
+  &[("english", "src/words.txt", "ABCDEFGHIJKLMNOPQRSTUVWXYZ")];
+
+// must match `utils::dictionary`, words of up to 8 letters packed in 5 bytes
+const MAX_LETTERS: usize = 8;
+const BYTES_PER_WORD: usize = 5;
+
+// packs a word into 5 bits per letter, left aligned so packed words sort like the words
+fn pack(word: &str, alphabet: &[char]) -> u64 {
+  let letters: Vec<char> = word.chars().collect();
+  assert!(
+    !letters.is_empty() && letters.len() <= MAX_LETTERS,
+    "{} doesn't fit in the dictionary",
+    word
+  );
+  let mut key: u64 = 0;
+  for letter in letters.iter() {
+    let index = match alphabet.iter().position(|l| l == letter) {
+      Some(index) => index,
+      None => panic!("{} has a letter that isn't in the alphabet", word),
+    };
+    key = key << 5 | (index + 1) as u64;
+  }
+  key << (5 * (MAX_LETTERS - letters.len()))
+}
+
+fn main() {
+  println!("cargo:rerun-if-changed=build.rs");
+  let out_dir = env::var("OUT_DIR").unwrap();
+  // without the words only the alphabets are compiled in, for tables with a merkle root
+  let embed_words = env::var("CARGO_FEATURE_WORD_LISTS").is_ok();
+
+  let mut word_lists = String::new();
+  for (name, file, alphabet) in WORD_LISTS.iter() {
+    println!("cargo:rerun-if-changed={}", file);
+    let alphabet: Vec<char> = alphabet.chars().collect();
+    assert!(alphabet.len() < 32, "{} has too many letters to pack", name);
+
+    let words = if embed_words {
+      fs::read_to_string(file).unwrap()
+    } else {
+      String::new()
+    };
+    // the id of a word is its line in the file, stamped NFTs refer to it
+    let mut keys: Vec<(u64, u32)> = words
+      .split_terminator('\n')
+      .enumerate()
+      .map(|(id, word)| (pack(word, &alphabet), id as u32))
+      .collect();
+    keys.sort_unstable();
+
+    let mut packed = vec![];
+    let mut moved_ids = vec![];
+    for (rank, (key, id)) in keys.iter().enumerate() {
+      if rank > 0 && keys[rank - 1].0 == *key {
+        panic!("{} has a duplicate at line {}", file, id);
+      }
+      packed.extend_from_slice(&key.to_be_bytes()[8 - BYTES_PER_WORD..]);
+      if rank as u32 != *id {
+        moved_ids.push(format!("({}, {})", rank, id));
+      }
+    }
+    fs::write(Path::new(&out_dir).join(format!("{}.bin", name)), packed).unwrap();
+
+    let symbols: Vec<String> = alphabet.iter().map(|l| format!("\"{}\"", l)).collect();
+    let prefix = name.to_uppercase();
+    word_lists.push_str(&format!(
+      "static {}_WORDS: &[u8] = include_bytes!(concat!(env!(\"OUT_DIR\"), \"/{}.bin\"));\n",
+      prefix, name
+    ));
+    word_lists.push_str(&format!(
+      "static {}_ALPHABET: [&str; {}] = [{}];\n",
+      prefix,
+      symbols.len(),
+      symbols.join(", ")
+    ));
+    word_lists.push_str(&format!(
+      "static {}_MOVED_IDS: [(u32, u32); {}] = [{}];\n",
+      prefix,
+      moved_ids.len(),
+      moved_ids.join(", ")
+    ));
+  }
+  fs::write(Path::new(&out_dir).join("word_lists.rs"), word_lists).unwrap();
+}
@@ -3,9 +3,10 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
-use secret_dreamscape::contract::{HandleMsg, InitMsg};
+use secret_dreamscape::contract::{HandleAnswer, HandleMsg, InitMsg};
 use secret_dreamscape::query::{
-  CanJoinResponse, GameState, PlayerStatus, QueryMsg, Result,
+  CanJoinResponse, ConfigResponse, GameState, HandHistoryResponse, PlayerStatus, PublicState,
+  QueryMsg, Result,
 };
 
 fn main() {
@@ -16,9 +17,13 @@ fn main() {
 
   export_schema(&schema_for!(InitMsg), &out_dir);
   export_schema(&schema_for!(HandleMsg), &out_dir);
+  export_schema(&schema_for!(HandleAnswer), &out_dir);
   export_schema(&schema_for!(CanJoinResponse), &out_dir);
+  export_schema(&schema_for!(ConfigResponse), &out_dir);
   export_schema(&schema_for!(GameState), &out_dir);
+  export_schema(&schema_for!(HandHistoryResponse), &out_dir);
   export_schema(&schema_for!(PlayerStatus), &out_dir);
+  export_schema(&schema_for!(PublicState), &out_dir);
   export_schema(&schema_for!(QueryMsg), &out_dir);
   export_schema(&schema_for!(Result), &out_dir);
 }
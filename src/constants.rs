@@ -11,6 +11,86 @@ pub(crate) static ALREADY_IN_GAME: &str = "You're already in the game and can't
 pub(crate) static WRONG_MATCHING_AMOUNT: &str = "You've sent the wrong bet amount.";
 pub(crate) static CANT_BET_IF_FOLDED: &str = "You can't bet if you're folded.";
 pub(crate) static CANT_PUT_CARD_IF_FOLDED: &str = "You can't put down a card if you're folded.";
+pub(crate) static CANT_FOLD_AFTER_PUTTING_DOWN: &str = "You can't fold after putting down a word.";
+pub(crate) static HAND_IS_OVER: &str = "This hand is already over.";
 pub(crate) static WRONG_PASSWORD: &str = "Wrong room password.";
 pub(crate) static CANT_CHECK_IF_NEED_TO_MATCH: &str =
   "You can't check if you're in the matching round.";
+pub(crate) static GAME_OVER: &str = "Game is over";
+pub(crate) static INVALID_DAMAGE: &str = "Losing a showdown must cost at least 1 hp.";
+pub(crate) static INVALID_TURN_TIMEOUT: &str = "Players need some time to act.";
+pub(crate) static NO_TIMEOUT_TO_CLAIM: &str = "Nobody has run out of time yet.";
+
+// seconds every player has to act before the round can be forced forward
+pub(crate) const DEFAULT_TURN_TIMEOUT: u64 = 120;
+
+// raises allowed in each of the matching rounds
+pub(crate) const DEFAULT_MAX_RAISES: u8 = 3;
+pub(crate) static INVALID_MIN_RAISE: &str = "The minimum raise must be more than 0.";
+pub(crate) static CANT_RAISE_NOW: &str = "You can only raise while matching a bet.";
+pub(crate) static RAISE_TOO_SMALL: &str = "Your raise is below the table's minimum raise.";
+pub(crate) static NO_RAISES_LEFT: &str = "No more raises are allowed this round.";
+pub(crate) static INVALID_MAX_PLAYERS: &str = "A table needs room for at least 2 players.";
+pub(crate) static INVALID_STARTING_HP: &str = "Players need at least 1 hp to start with.";
+pub(crate) static INVALID_HAND_SIZE: &str = "Hands need between 1 and 249 cards.";
+pub(crate) static INVALID_RIVER_SIZE: &str = "The river can have at most 6 cards.";
+pub(crate) static NOT_ENOUGH_CARDS: &str =
+  "The deck doesn't have enough cards to deal every hand and the river.";
+pub(crate) static INVALID_GOLD_CARDS: &str = "The deck doesn't have that many cards to make gold.";
+pub(crate) static INVALID_RAKE: &str = "The rake can't be more than 100%.";
+pub(crate) static INVALID_MIN_BET: &str = "The minimum bet must be more than 0.";
+pub(crate) static INVALID_LETTER_POINTS: &str =
+  "The dictionary needs points for every letter of its alphabet.";
+pub(crate) static INVALID_DISTRIBUTION: &str =
+  "The deck needs at least one card and only letters of the dictionary's alphabet.";
+pub(crate) static INVALID_MERKLE_ROOT: &str = "The dictionary's merkle root must be 32 bytes.";
+pub(crate) static NO_WORD_LIST: &str =
+  "This contract has no word lists, the dictionary needs a merkle root.";
+pub(crate) static INVALID_WORD_PROOF: &str =
+  "The proof doesn't show your word is in the dictionary.";
+pub(crate) static EMPTY_WORD: &str = "You need to put down at least one card.";
+pub(crate) static NOT_A_WORD: &str = "That word isn't in the table's dictionary.";
+pub(crate) static WRONG_BLANK_LETTERS: &str =
+  "Every blank card in your word needs exactly one letter.";
+pub(crate) static INVALID_BLANK_LETTER: &str =
+  "A blank can only be a letter of the table's alphabet.";
+pub(crate) static NOT_ADMIN: &str = "Only the table's admin can do that.";
+pub(crate) static CANT_CHANGE_DICTIONARY_MID_HAND: &str =
+  "The dictionary can only be changed between hands.";
+pub(crate) static INVALID_BLINDS: &str = "The big blind can't be smaller than the small blind.";
+pub(crate) static BET_TOO_SMALL: &str = "Less than the minimum bet of this table.";
+pub(crate) static INVALID_DENOM: &str = "The table's currency needs a denom.";
+pub(crate) static WRONG_CURRENCY: &str = "You can only buy chips in the table's currency.";
+pub(crate) static BUY_WITH_TOKEN: &str =
+  "This table takes its token, Send it to the table with a BuyChips message.";
+pub(crate) static NOT_THE_TABLE_TOKEN: &str = "Only the table's token can buy chips.";
+pub(crate) static NO_RECEIVE_MSG: &str = "Tokens sent to the table need a BuyChips message.";
+pub(crate) static CANT_BUY_MID_HAND: &str =
+  "You can only buy chips between hands or before you bet in this one.";
+pub(crate) static CANT_CASH_OUT_MID_HAND: &str = "You can only cash out between hands.";
+pub(crate) static INVALID_CASH_OUT: &str = "You can't cash out more chips than you have.";
+pub(crate) static WAITING_FOR_NEXT_HAND: &str =
+  "You joined mid-hand and will be dealt in when the next one starts.";
+pub(crate) static NOT_YOUR_TURN: &str = "It's not your turn to act.";
+pub(crate) static CANT_MATCH_NOW: &str = "There's no bet to match right now.";
+pub(crate) static FUNDS_NOT_ACCEPTED: &str =
+  "This action doesn't take any funds, send them with BuyChips instead.";
+// most settled hands a single GetHandHistory returns
+pub(crate) const MAX_HAND_HISTORY_PAGE: u32 = 30;
+pub(crate) static WRONG_VIEWING_KEY: &str =
+  "Wrong viewing key for this address or viewing key not set.";
+pub(crate) static INVALID_PERMIT: &str = "This permit isn't valid for this table.";
+pub(crate) static PERMIT_REVOKED: &str = "This permit was revoked.";
+pub(crate) static CANT_REVEAL_NOW: &str = "The deck has already been shuffled.";
+pub(crate) static ALREADY_REVEALED: &str = "You already revealed your secret.";
+pub(crate) static SECRET_DOESNT_MATCH_COMMITMENT: &str =
+  "Your secret doesn't match the commitment you joined with.";
+pub(crate) static NO_CARDS_DEALT_YET: &str = "No cards have been dealt yet.";
+pub(crate) static OUT_OF_CARDS: &str = "There are no cards left to deal.";
+
+// version of the layout tables are stored in, bump it and add a step to
+// `utils::migration::migrate_if_needed` whenever a stored record changes
+pub(crate) const SCHEMA_VERSION: u8 = 2;
+pub(crate) static OUTDATED_SCHEMA: &str =
+  "This table was stored by an older version, send any action to upgrade it.";
+pub(crate) static UNKNOWN_SCHEMA_VERSION: &str = "This table was stored by a newer version.";
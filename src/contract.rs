@@ -1,22 +1,46 @@
 use std::cmp::Ordering;
 
 use cosmwasm_std::{
-  Api, BankMsg, Coin, CosmosMsg, Env, Extern, HandleResponse, HandleResult, HumanAddr,
-  InitResponse, InitResult, Querier, StdError, StdResult, Storage, Uint128,
+  from_binary, to_binary, Api, Binary, CosmosMsg, Env, Extern, HandleResponse, HandleResult,
+  HumanAddr, InitResponse, InitResult, Querier, StdError, StdResult, Storage, Uint128,
 };
 use schemars::JsonSchema;
+use secret_toolkit::snip20::register_receive_msg;
 use secret_toolkit::utils::HandleCallback;
 use serde::{Deserialize, Serialize};
-use serde_json_wasm as serde_json;
+use sha2::{Digest, Sha256};
 
 use crate::constants::{
-  ALREADY_IN_GAME, ALREADY_PUT_DOWN, CANT_BET_IF_FOLDED, CANT_CHECK_IF_NEED_TO_MATCH,
-  CANT_PUT_CARD_AT_THE_MOMENT, CANT_PUT_CARD_IF_FOLDED, CANT_USE_CARD_TWICE, GAME_FULL,
-  NOT_IN_GAME, NOT_IN_YOUR_HAND, NO_NEXT_TURN, WRONG_MATCHING_AMOUNT, WRONG_PASSWORD,
+  ALREADY_IN_GAME, ALREADY_PUT_DOWN, ALREADY_REVEALED, BET_TOO_SMALL, BUY_WITH_TOKEN,
+  CANT_BET_IF_FOLDED, CANT_BUY_MID_HAND, CANT_CASH_OUT_MID_HAND, CANT_CHANGE_DICTIONARY_MID_HAND,
+  CANT_CHECK_IF_NEED_TO_MATCH, CANT_FOLD_AFTER_PUTTING_DOWN, CANT_MATCH_NOW,
+  CANT_PUT_CARD_AT_THE_MOMENT, CANT_PUT_CARD_IF_FOLDED, CANT_RAISE_NOW, CANT_REVEAL_NOW,
+  CANT_USE_CARD_TWICE, EMPTY_WORD, FUNDS_NOT_ACCEPTED, GAME_FULL, GAME_OVER, HAND_IS_OVER,
+  INVALID_BLANK_LETTER, INVALID_BLINDS, INVALID_CASH_OUT, INVALID_DAMAGE, INVALID_DENOM,
+  INVALID_DISTRIBUTION, INVALID_GOLD_CARDS, INVALID_HAND_SIZE, INVALID_LETTER_POINTS,
+  INVALID_MAX_PLAYERS, INVALID_MERKLE_ROOT, INVALID_MIN_BET, INVALID_MIN_RAISE, INVALID_RAKE,
+  INVALID_RIVER_SIZE, INVALID_STARTING_HP, INVALID_TURN_TIMEOUT, INVALID_WORD_PROOF, NOT_ADMIN,
+  NOT_A_WORD, NOT_ENOUGH_CARDS, NOT_IN_GAME, NOT_IN_YOUR_HAND, NOT_THE_TABLE_TOKEN, NOT_YOUR_TURN,
+  NO_CARDS_DEALT_YET, NO_NEXT_TURN, NO_RAISES_LEFT, NO_RECEIVE_MSG, NO_TIMEOUT_TO_CLAIM,
+  NO_WORD_LIST, RAISE_TOO_SMALL, SECRET_DOESNT_MATCH_COMMITMENT, WAITING_FOR_NEXT_HAND,
+  WRONG_BLANK_LETTERS, WRONG_CURRENCY, WRONG_MATCHING_AMOUNT, WRONG_PASSWORD,
 };
-use crate::game_state::{Card, GameBoard, GameRound, Player, PlayerAction, State, Word};
-use crate::utils::cards::{find_word_id, generate_deck, get_n_cards, get_rng, get_score_for_word};
-use crate::utils::general::get_non_folded_players;
+use crate::game_state::{
+  Card, Currency, DealtHand, Dictionary, GameBoard, GameRound, HandOutcome, HandRecord,
+  InvalidWordPolicy, LoggedAction, Payout, Player, PlayerAction, Pot, ScoredWord, State,
+  TableConfig, Withdrawal, Word, WordProof,
+};
+use crate::utils::cards::{
+  discard, generate_deck, get_n_cards, get_rank_for_played_word, get_rng, get_score_for_played_word,
+};
+use crate::utils::chips::{fund_jackpot, send_chips};
+use crate::utils::dictionary::{alphabet, find_word_id, verify_word_proof};
+use crate::utils::events::{action_logs, describe_action, Snapshot};
+use crate::utils::general::{get_non_folded_players, is_in_hand, is_river_shown};
+use crate::utils::migration::migrate_if_needed;
+use crate::utils::permits::revoke_permit;
+use crate::utils::storage::{load_player, load_state, save_state};
+use crate::utils::viewing_keys::{new_viewing_key, set_viewing_key};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -32,6 +56,11 @@ pub struct InitMsg {
   pub max_buy: u64,
   pub jackpot_addr: HumanAddr,
   pub jackpot_hash: String,
+  pub table: Option<TableConfig>,
+  pub currency: Option<Currency>,
+  pub dictionary: Option<Dictionary>,
+  // defaults to whoever creates the table
+  pub admin: Option<HumanAddr>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
@@ -55,19 +84,49 @@ pub fn init<S: Storage, A: Api, Q: Querier>(
   msg: InitMsg,
 ) -> InitResult {
   let block_time = env.block.time;
+  let dictionary = msg.dictionary.unwrap_or_default();
+  validate_dictionary(&dictionary)?;
+  let table = msg.table.unwrap_or_default();
+  validate_table_config(&table, dictionary.cards_in_deck())?;
+  let currency = msg.currency.unwrap_or_default();
+  let mut messages: Vec<CosmosMsg> = vec![];
+  match &currency {
+    Currency::Native { denom } => {
+      if denom.is_empty() {
+        return Err(StdError::generic_err(INVALID_DENOM));
+      }
+    }
+    Currency::Snip20 { addr, hash } => {
+      // the token has to know about us to call Receive when chips are bought
+      messages.push(register_receive_msg(
+        env.contract_code_hash.clone(),
+        None,
+        256,
+        hash.clone(),
+        addr.clone(),
+      )?);
+    }
+  }
   let state = State {
     players: vec![],
     winner: None,
     game_board: GameBoard {
       round: GameRound::None,
-      winner_for_turn: None,
       words: vec![],
       river: vec![],
       pool: 0,
       turn: 0,
-      rake_percentage: 10,
+      round_deadline: None,
+      pots: vec![],
+      raises: 0,
+      pending_withdrawals: vec![],
+      dealer: 0,
+      action_on: None,
+      join_queue: vec![],
+      hand_log: HandRecord::default(),
     },
     deck: vec![],
+    discard_pile: vec![],
     can_join: true,
     started_time: block_time,
     level_design: msg.bg,
@@ -78,6 +137,11 @@ pub fn init<S: Storage, A: Api, Q: Querier>(
     max_buy: msg.max_buy,
     jackpot_addr: msg.jackpot_addr,
     jackpot_hash: msg.jackpot_hash,
+    contract_addr: env.contract.address.clone(),
+    table,
+    currency,
+    dictionary,
+    admin: Some(msg.admin.unwrap_or_else(|| env.message.sender.clone())),
   };
 
   let callback_msg = PhonebookHandleMsg::RegisteredCallback {
@@ -87,15 +151,15 @@ pub fn init<S: Storage, A: Api, Q: Querier>(
     referrer: msg.callback_hash.clone(),
   };
 
-  let cosmos_msg =
-    callback_msg.to_cosmos_msg(msg.callback_hash.clone(), msg.callback_addr.clone(), None)?;
+  messages.insert(
+    0,
+    callback_msg.to_cosmos_msg(msg.callback_hash.clone(), msg.callback_addr.clone(), None)?,
+  );
 
-  deps
-    .storage
-    .set(b"state", &serde_json::to_vec(&state).unwrap());
+  save_state(&mut deps.storage, &state)?;
 
   Ok(InitResponse {
-    messages: vec![cosmos_msg],
+    messages,
     log: vec![],
   })
 }
@@ -122,25 +186,82 @@ impl HandleCallback for SecretDreamscapeJackpot {
 #[serde(rename_all = "snake_case")]
 pub enum HandleMsg {
   Join {
-    secret: u64,
+    commitment: Binary,
     password: String,
     nfts: Vec<SecretDreamscapeNFT>,
   },
   BuyChips {},
+  Receive {
+    sender: HumanAddr,
+    from: HumanAddr,
+    amount: Uint128,
+    memo: Option<String>,
+    msg: Option<Binary>,
+  },
   Bet {
     amount: u64,
   },
   Match {
     amount: u64,
   },
+  Raise {
+    amount: u64,
+  },
   Fold {},
   Check {},
+  CashOut {
+    amount: u64,
+  },
   Leave {},
   PutDownCard {
     indexes: Vec<u8>,
     opened_dictionary: bool,
+    // required to count the word on tables with a merkle root
+    proof: Option<WordProof>,
+    // letter of every blank card in the word, in word order
+    #[serde(default)]
+    blank_letters: Vec<u8>,
+  },
+  Reveal {
+    secret: Binary,
   },
   RequestNextTurn {},
+  ClaimTimeout {},
+  CreateViewingKey {
+    entropy: String,
+  },
+  SetViewingKey {
+    key: String,
+  },
+  RevokePermit {
+    permit_name: String,
+  },
+  SetDictionaryRoot {
+    root: Binary,
+  },
+}
+
+// payload of a snip20 Send to the table
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+  BuyChips {},
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HandleAnswer {
+  CreateViewingKey {
+    key: String,
+  },
+  // where the table stands after a game action, `chips` is None once the player has left
+  GameAction {
+    round: GameRound,
+    turn: u32,
+    pool: u64,
+    chips: Option<u64>,
+    action_on: Option<HumanAddr>,
+  },
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
@@ -148,7 +269,7 @@ pub enum HandleMsg {
 pub enum StampHandleMsg {
   Stamp {
     nft_id: String,
-    word_id: u16,
+    word_id: u32,
     callee: HumanAddr,
   },
 }
@@ -162,13 +283,85 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
   env: Env,
   msg: HandleMsg,
 ) -> HandleResult {
-  let mut state: State = serde_json::from_slice(&deps.storage.get(b"state").unwrap()).unwrap();
-  if state.winner.is_some() {
-    return Err(StdError::generic_err("Game is over"));
+  migrate_if_needed(&mut deps.storage, &env)?;
+  let mut state = load_state(&deps.storage)?;
+  let manages_keys = matches!(
+    msg,
+    HandleMsg::CreateViewingKey { .. }
+      | HandleMsg::SetViewingKey { .. }
+      | HandleMsg::RevokePermit { .. }
+  );
+  if state.winner.is_some() && !manages_keys {
+    return Err(StdError::generic_err(GAME_OVER));
   }
+  // funds sent along with anything but BuyChips would never be credited to anyone
+  let buys_chips = matches!(msg, HandleMsg::BuyChips { .. });
+  if !buys_chips && !env.message.sent_funds.is_empty() {
+    return Err(StdError::generic_err(FUNDS_NOT_ACCEPTED));
+  }
+
+  // every action is logged the same way for indexers following the table
+  let before = Snapshot::of(&state);
+  let (action, player, amount) = describe_action(&env, &msg);
+  // the action leaves the state as it saved it, so it isn't loaded again for the logs
+  let mut response = handle_action(deps, env, msg, &mut state)?;
+  response.log = action_logs(action, &player, amount, &before, &state);
+  if !manages_keys {
+    response.data = Some(to_binary(&HandleAnswer::GameAction {
+      round: state.game_board.round.clone(),
+      turn: state.game_board.turn,
+      pool: state.game_board.pool,
+      chips: state
+        .players
+        .iter()
+        .find(|p| p.addr == player)
+        .map(|p| p.chips),
+      action_on: state.game_board.action_on.clone(),
+    })?);
+  }
+  Ok(response)
+}
+
+fn handle_action<S: Storage, A: Api, Q: Querier>(
+  deps: &mut Extern<S, A, Q>,
+  env: Env,
+  msg: HandleMsg,
+  state: &mut State,
+) -> HandleResult {
   match msg {
+    HandleMsg::CreateViewingKey { entropy } => {
+      let key = new_viewing_key(&env, &entropy);
+      set_viewing_key(&mut deps.storage, &env.message.sender, &key);
+      Ok(HandleResponse {
+        messages: vec![],
+        log: vec![],
+        data: Some(to_binary(&HandleAnswer::CreateViewingKey { key })?),
+      })
+    }
+    HandleMsg::SetViewingKey { key } => {
+      set_viewing_key(&mut deps.storage, &env.message.sender, &key);
+      Ok(HandleResponse::default())
+    }
+    HandleMsg::RevokePermit { permit_name } => {
+      revoke_permit(&mut deps.storage, &env.message.sender, &permit_name);
+      Ok(HandleResponse::default())
+    }
+    HandleMsg::SetDictionaryRoot { root } => {
+      if state.admin.as_ref() != Some(&env.message.sender) {
+        return Err(StdError::generic_err(NOT_ADMIN));
+      }
+      // words already down were proven against the old root
+      if !is_between_hands(state) {
+        return Err(StdError::generic_err(CANT_CHANGE_DICTIONARY_MID_HAND));
+      }
+      validate_merkle_root(&root)?;
+      state.dictionary.merkle_root = Some(root);
+
+      save_state(&mut deps.storage, state)?;
+      Ok(HandleResponse::default())
+    }
     HandleMsg::Join {
-      secret,
+      commitment,
       password,
       nfts,
     } => {
@@ -178,7 +371,7 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
         }
       }
 
-      if state.players.len() == 4 {
+      if state.players.len() >= state.table.max_players as usize {
         return Err(StdError::generic_err(GAME_FULL));
       }
 
@@ -188,100 +381,128 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
         }
       }
 
-      state.players.push(Player {
-        addr: env.clone().message.sender,
-        secret,
-        hand: vec![],
-        hp: 5,
-        bet: 0,
-        bet2: 0,
-        folded: state.players.len() > 1,
-        checked: false,
-        checked2: false,
-        opened_dictionary: false,
-        last_action: if state.players.len() > 1 {
-          Some(PlayerAction::Folded)
-        } else {
-          None
+      let cards_dealt = is_deck_dealt(state);
+      let mut seat: u8 = 0;
+      while state.players.iter().any(|p| p.seat == seat) {
+        seat += 1;
+      }
+      let index = state.players.iter().filter(|p| p.seat < seat).count();
+      state.players.insert(
+        index,
+        Player {
+          addr: env.clone().message.sender,
+          commitment,
+          revealed_secret: None,
+          hand: vec![],
+          hp: state.table.starting_hp,
+          bet: 0,
+          bet2: 0,
+          folded: false,
+          checked: false,
+          checked2: false,
+          opened_dictionary: false,
+          last_action: None,
+          nfts,
+          chips: 0,
+          left: false,
+          seat,
+          waiting: cards_dealt,
         },
-        nfts,
-        chips: 0,
-      });
-
-      if state.players.len() == 2 {
-        // after the second player joins we need to start generating decks
-        state.deck = generate_deck(get_rng(&state, &env));
-
-        state.game_board.river = get_n_cards(&mut state, 5);
+      );
+
+      let mut messages = vec![];
+      if cards_dealt {
+        // late joiners sit out the hand in play and are dealt in when the next one starts
+        state.game_board.join_queue.push(env.message.sender.clone());
+      } else {
+        messages = deal_first_hand_if_everyone_is_ready(state, &env)?;
       }
 
-      if state.players.len() >= 2 {
-        for i in 0..state.players.len() {
-          if state.players[i].hand.is_empty() {
-            state.players[i].hand = get_n_cards(&mut state, 5).to_owned();
-          }
-        }
+      save_state(&mut deps.storage, state)?;
+      send_messages_if_any(messages)
+    }
+    HandleMsg::Reveal { secret } => {
+      let requester = get_requesting_player(&deps, env.clone())?;
+      if is_deck_dealt(state) {
+        return Err(StdError::generic_err(CANT_REVEAL_NOW));
+      }
+      if requester.revealed_secret.is_some() {
+        return Err(StdError::generic_err(ALREADY_REVEALED));
+      }
+      if Sha256::digest(secret.as_slice())[..] != *requester.commitment.as_slice() {
+        return Err(StdError::generic_err(SECRET_DOESNT_MATCH_COMMITMENT));
+      }
 
-        if state.game_board.round == GameRound::None {
-          state.game_board.round = GameRound::Blind;
+      for i in 0..state.players.len() {
+        if state.players[i].addr == requester.addr {
+          state.players[i].revealed_secret = Some(secret.clone());
         }
       }
+      let messages = deal_first_hand_if_everyone_is_ready(state, &env)?;
 
-      deps
-        .storage
-        .set(b"state", &serde_json::to_vec(&state).unwrap());
-      Ok(HandleResponse::default())
+      save_state(&mut deps.storage, state)?;
+      send_messages_if_any(messages)
     }
     HandleMsg::BuyChips {} => {
       get_requesting_player(&deps, env.clone())?;
-      if env.message.sent_funds.len() != 1 {
-        return Err(StdError::generic_err(
-          "You can only send SCRT to this function",
-        ));
-      }
-      if env.message.sent_funds[0].denom != "uscrt" {
-        return Err(StdError::generic_err(
-          "You can only send SCRT to this function",
-        ));
+      let denom = match &state.currency {
+        Currency::Native { denom } => denom.clone(),
+        Currency::Snip20 { .. } => return Err(StdError::generic_err(BUY_WITH_TOKEN)),
+      };
+      if env.message.sent_funds.len() != 1 || env.message.sent_funds[0].denom != denom {
+        return Err(StdError::generic_err(WRONG_CURRENCY));
       }
 
       let amount = env.message.sent_funds[0].amount.u128() as u64;
+      let messages = buy_chips(state, &env.message.sender, amount, &env)?;
 
-      if amount < state.min_buy || amount > state.max_buy {
-        return Err(StdError::generic_err(
-          "You didn't send enough SCRT to this function or you sent too much",
-        ));
-      }
+      save_state(&mut deps.storage, state)?;
 
-      for i in 0..state.players.len() {
-        if state.players[i].addr == env.message.sender {
-          if state.players[i].chips + amount > state.max_buy {
-            return Err(StdError::generic_err(
-              "You can't buy more chips than the maximum amount",
-            ));
+      send_messages_if_any(messages)
+    }
+    HandleMsg::Receive {
+      from, amount, msg, ..
+    } => {
+      match &state.currency {
+        Currency::Snip20 { addr, .. } if *addr == env.message.sender => {}
+        _ => return Err(StdError::generic_err(NOT_THE_TABLE_TOKEN)),
+      }
+      let msg = msg.ok_or_else(|| StdError::generic_err(NO_RECEIVE_MSG))?;
+      let mut messages = vec![];
+      match from_binary(&msg)? {
+        ReceiveMsg::BuyChips {} => {
+          match load_player(&deps.storage, &from)? {
+            Some(player) if !player.left => {}
+            _ => return Err(StdError::generic_err(NOT_IN_GAME)),
           }
-          state.players[i].chips += amount;
+          messages = buy_chips(state, &from, amount.u128() as u64, &env)?;
         }
       }
 
-      deps
-        .storage
-        .set(b"state", &serde_json::to_vec(&state).unwrap());
+      save_state(&mut deps.storage, state)?;
 
-      Ok(HandleResponse::default())
+      send_messages_if_any(messages)
     }
     HandleMsg::PutDownCard {
       indexes,
       opened_dictionary,
+      proof,
+      blank_letters,
     } => {
-      require_at_least_two_players(&mut state)?;
+      require_at_least_two_players(state)?;
       let requester = get_requesting_player(&deps, env.clone())?;
       if state.game_board.round != GameRound::Choice {
         return Err(StdError::generic_err(CANT_PUT_CARD_AT_THE_MOMENT));
       }
+      if requester.waiting {
+        return Err(StdError::generic_err(WAITING_FOR_NEXT_HAND));
+      }
       if requester.folded {
         return Err(StdError::generic_err(CANT_PUT_CARD_IF_FOLDED));
       }
+      if state.game_board.hand_log.is_settled() {
+        return Err(StdError::generic_err(HAND_IS_OVER));
+      }
 
       for i in 0..state.game_board.words.len() {
         if state.game_board.words[i].player_addr == requester.addr {
@@ -290,50 +511,92 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
       }
 
       let mut indexes_used: Vec<u8> = vec![];
+      let mut blanks_used = 0;
       let mut word: Vec<Card> = vec![];
       for index in indexes.iter() {
         if indexes_used.contains(index) {
           return Err(StdError::generic_err(CANT_USE_CARD_TWICE));
         }
-        if index > &(4_u8) && index < &(250_u8) {
+        if *index as usize >= requester.hand.len() && *index < 250_u8 {
           // 250...255 can be treated as the indexes for the cards on the board
           return Err(StdError::generic_err(NOT_IN_YOUR_HAND));
         }
-        word.push(if index >= &(250_u8) {
+        if *index as usize >= 250 + state.game_board.river.len() {
+          return Err(StdError::generic_err(NOT_IN_YOUR_HAND));
+        }
+        let mut card = if index >= &(250_u8) {
           state.game_board.river[(*index as usize) - 250].clone()
         } else {
-          requester.hand[(*index as usize)].clone()
-        });
+          requester.hand[*index as usize].clone()
+        };
+        // blanks take the letters they were given in the order they're in the word
+        if card.blank {
+          card.letter = match blank_letters.get(blanks_used) {
+            Some(letter) => *letter,
+            None => return Err(StdError::generic_err(WRONG_BLANK_LETTERS)),
+          };
+          if card.letter as usize >= alphabet(&state.dictionary.word_list).len() {
+            return Err(StdError::generic_err(INVALID_BLANK_LETTER));
+          }
+          blanks_used += 1;
+        }
+        word.push(card);
         indexes_used.push(*index);
       }
+      if blanks_used != blank_letters.len() {
+        return Err(StdError::generic_err(WRONG_BLANK_LETTERS));
+      }
       let mut messages: Vec<CosmosMsg> = vec![];
       let mut new_hand: Vec<Card> = vec![];
+      let mut played_cards: Vec<Card> = vec![];
       for i in 0..requester.hand.len() {
-        if !indexes.contains(&(i as u8)) {
+        if indexes.contains(&(i as u8)) {
+          played_cards.push(requester.hand[i].clone());
+        } else {
           new_hand.push(requester.hand[i].clone());
         }
       }
-      let word_string: String = word
-        .iter()
-        .map(|c| ('A' as u8 + c.letter) as char)
-        .collect::<Vec<char>>()
-        .into_iter()
-        .collect();
-      for i in 0..word.len() {
-        let card = word[i].clone();
-        for j in 0..requester.nfts.len() {
-          let letter = (('A' as u8 + card.letter as u8) as char).to_string();
-          let nft = requester.nfts[j].clone();
-          if nft.letter == letter && nft.gold == card.gold {
-            let word_id = find_word_id(word_string.as_str()).unwrap_or(0);
-            let message = StampHandleMsg::Stamp {
-              nft_id: nft.clone().id,
-              word_id: word_id as u16,
-              callee: env.clone().message.sender,
-            };
-            let cosmos_msg =
-              message.to_cosmos_msg(state.stamp_hash.clone(), state.stamp_addr.clone(), None)?;
-            messages.push(cosmos_msg);
+      discard(state, played_cards);
+      let letters: Vec<u8> = word.iter().map(|c| c.letter).collect();
+      let word_id = match (&state.dictionary.merkle_root, &proof) {
+        (None, _) => find_word_id(&state.dictionary.word_list, &letters).map(|id| id as u32),
+        (Some(root), Some(proof)) => {
+          if !verify_word_proof(root, &letters, proof) {
+            return Err(StdError::generic_err(INVALID_WORD_PROOF));
+          }
+          Some(proof.word_id)
+        }
+        // nothing shows the word is in the dictionary
+        (Some(_), None) => None,
+      };
+      if state.table.invalid_words == InvalidWordPolicy::Reject {
+        if word.is_empty() {
+          return Err(StdError::generic_err(EMPTY_WORD));
+        }
+        if word_id.is_none() {
+          return Err(StdError::generic_err(NOT_A_WORD));
+        }
+      }
+      // only words in the dictionary have an id to stamp
+      if let Some(word_id) = word_id {
+        let symbols = alphabet(&state.dictionary.word_list);
+        for card in word.iter() {
+          // a blank isn't the letter it stands for, so it has no nft to stamp
+          if card.blank {
+            continue;
+          }
+          for nft in requester.nfts.iter() {
+            let letter = symbols[card.letter as usize];
+            if nft.letter == letter && nft.gold == card.gold {
+              let message = StampHandleMsg::Stamp {
+                nft_id: nft.id.clone(),
+                word_id,
+                callee: env.clone().message.sender,
+              };
+              let cosmos_msg =
+                message.to_cosmos_msg(state.stamp_hash.clone(), state.stamp_addr.clone(), None)?;
+              messages.push(cosmos_msg);
+            }
           }
         }
       }
@@ -341,65 +604,36 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
       state.game_board.words.push(Word {
         cards: word,
         player_addr: requester.addr.clone(),
+        word_id,
       });
 
       for i in 0..state.players.len() {
         if state.players[i].addr == requester.addr {
           state.players[i].opened_dictionary = opened_dictionary;
-          state.players[i].last_action = Some(PlayerAction::ChoseWord);
+          record_action(state, i, PlayerAction::ChoseWord);
           state.players[i].hand = new_hand.clone();
           break;
         }
       }
 
-      let non_folded_players = get_non_folded_players(&state);
-
-      if state.game_board.words.len() == non_folded_players.len() {
-        let winners = get_winners_for_turn(&state);
-
-        let mut winner_indexes: Vec<usize> = vec![];
+      messages.append(&mut resolve_showdown_if_all_words_are_down(state, &env)?);
 
-        for i in 0..winners.len() {
-          for j in 0..state.players.len() {
-            if state.players[j].addr == winners[i].player_addr {
-              winner_indexes.push(j);
-              break;
-            }
-          }
-        }
-
-        state.game_board.winner_for_turn = Some(winners[0].clone().player_addr);
-
-        for i in 0..state.players.len() {
-          // if !winner_addresses.contains(&state.players[i].addr.clone()) && state.players[i].hp > 0 {
-          //  state.players[i].hp -= 1;
-          // }
-          state.players[i].bet = 0;
-          state.players[i].bet2 = 0;
-        }
-
-        let transfers = give_winners_their_money(&mut state, winner_indexes)?;
-        for t in transfers {
-          messages.push(t.clone());
-        }
-      }
-
-      deps
-        .storage
-        .set(b"state", &serde_json::to_vec(&state).unwrap());
+      save_state(&mut deps.storage, state)?;
       send_messages_if_any(messages)
     }
     HandleMsg::Bet { amount } => {
-      require_at_least_two_players(&mut state)?;
+      require_at_least_two_players(state)?;
 
       let player = get_requesting_player(&deps, env.clone())?;
 
       if player.folded {
         return Err(StdError::generic_err(CANT_BET_IF_FOLDED));
       }
+      require_players_turn(state, &player)?;
 
-      if amount < 125_000 {
-        return Err(StdError::generic_err("Less than  0.125scrt"));
+      // going all-in is allowed even when the player has less than the minimum bet left
+      if amount < state.table.min_bet && amount != player.chips {
+        return Err(StdError::generic_err(BET_TOO_SMALL));
       }
 
       if player.chips < amount {
@@ -416,47 +650,59 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
           if state.players[i].folded {
             return Err(StdError::generic_err(CANT_BET_IF_FOLDED));
           }
-          state.players[i].last_action = Some(PlayerAction::SentBet(amount));
           if state.game_board.round == GameRound::Blind {
             state.players[i].bet += amount;
           } else {
             state.players[i].bet2 += amount;
           }
           state.players[i].chips -= amount;
+          let action = if state.players[i].chips == 0 {
+            PlayerAction::AllIn(amount)
+          } else {
+            PlayerAction::SentBet(amount)
+          };
+          record_action(state, i, action);
         }
       }
 
-      advance_turn_if_necessary(&mut state);
+      advance_turn_if_necessary(state, &env);
 
-      deps
-        .storage
-        .set(b"state", &serde_json::to_vec(&state).unwrap());
+      save_state(&mut deps.storage, state)?;
       Ok(HandleResponse::default())
     }
     HandleMsg::RequestNextTurn {} => {
-      require_at_least_two_players(&mut state)?;
-      get_requesting_player(&deps, env)?;
+      let mut messages = vec![];
+      require_at_least_two_players(state)?;
+      get_requesting_player(&deps, env.clone())?;
 
-      match state.game_board.winner_for_turn {
+      match state.game_board.hand_log.outcome {
         None => return Err(StdError::generic_err(NO_NEXT_TURN)),
-        Some(ref _winner) => {
-          let zero_count = state.players.iter().filter(|&p| p.hp == 0).count();
-          if state.players.len() == (zero_count + 1) {
-            // if there's a winner
-            state.winner = state.game_board.winner_for_turn.clone();
-          }
+        Some(_) => {
+          release_seats_of_players_who_left(state);
+          require_at_least_two_players(state)?;
+          move_dealer_button(state);
           state.game_board.turn += 1;
-          state.game_board.winner_for_turn = None;
+          state.game_board.hand_log.outcome = None;
           state.game_board.words = vec![];
-          state.game_board.river = get_n_cards(&mut state, 5);
+          let old_river = state.game_board.river.clone();
+          discard(state, old_river);
+          let river_size = state.table.river_size;
+          state.game_board.river = get_n_cards(state, river_size, &env)?;
           state.game_board.round = GameRound::Blind;
           state.game_board.pool = 0;
+          state.game_board.pots = vec![];
+          state.game_board.raises = 0;
+          reset_round_deadline(state, &env);
+          deal_in_join_queue(state);
           for i in 0..state.players.len() {
             state.players[i].bet = 0;
             state.players[i].bet2 = 0;
-            if state.players[i].chips < 125_000 {
-              state.players[i].folded = true;
-              state.players[i].last_action = Some(PlayerAction::Folded);
+            if state.players[i].hp == 0 {
+              // eliminated players stay out of the rotation for the rest of the game
+              continue;
+            }
+            if state.players[i].chips < state.table.min_bet {
+              sit_out(state, i);
             } else {
               state.players[i].folded = false;
               state.players[i].last_action = None;
@@ -465,85 +711,177 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
             state.players[i].checked2 = false;
             state.players[i].opened_dictionary = false;
             let mut new_hand = state.players[i].hand.clone();
-            if new_hand.len() < 5 {
-              let count: u8 = 5 - new_hand.len() as u8;
-              new_hand.append(&mut get_n_cards(&mut state, count));
+            if new_hand.len() < state.table.hand_size as usize {
+              let count: u8 = state.table.hand_size - new_hand.len() as u8;
+              new_hand.append(&mut get_n_cards(state, count, &env)?);
               state.players[i].hand = new_hand.clone();
             }
           }
-          deps
-            .storage
-            .set(b"state", &serde_json::to_vec(&state).unwrap());
+          messages = start_hand(state, &env)?;
+          save_state(&mut deps.storage, state)?;
         }
       }
 
-      Ok(HandleResponse::default())
+      Ok(HandleResponse {
+        messages,
+        log: vec![],
+        data: None,
+      })
     }
     HandleMsg::Match { amount } => {
-      require_at_least_two_players(&mut state)?;
+      require_at_least_two_players(state)?;
 
-      let highest_bet = get_highest_bet(&state);
+      if state.game_board.round != GameRound::Matching
+        && state.game_board.round != GameRound::Matching2
+      {
+        return Err(StdError::generic_err(CANT_MATCH_NOW));
+      }
+
+      let highest_bet = get_highest_bet(state);
 
       let player = get_requesting_player(&deps, env.clone())?;
 
       if player.folded {
         return Err(StdError::generic_err(CANT_BET_IF_FOLDED));
       }
+      require_players_turn(state, &player)?;
+
+      for i in 0..state.players.len() {
+        if state.players[i].addr == env.message.sender {
+          let current_bet = if state.game_board.round == GameRound::Matching {
+            state.players[i].bet
+          } else {
+            state.players[i].bet2
+          };
+          // calling costs exactly what's missing to reach the highest bet, or everything the
+          // player has left when that isn't enough. Any other amount is rejected instead of
+          // being charged, raising goes through Raise
+          let call = (highest_bet - current_bet).min(state.players[i].chips);
+          if amount != call {
+            return Err(StdError::generic_err(WRONG_MATCHING_AMOUNT));
+          }
+          state.game_board.pool += amount;
+          if state.game_board.round == GameRound::Matching {
+            state.players[i].bet += amount;
+          } else {
+            state.players[i].bet2 += amount;
+          }
+          state.players[i].chips -= amount;
+          let action = if state.players[i].chips == 0 {
+            PlayerAction::AllIn(amount)
+          } else {
+            PlayerAction::MatchedBet
+          };
+          record_action(state, i, action);
+        }
+      }
+
+      advance_turn_if_necessary(state, &env);
+
+      save_state(&mut deps.storage, state)?;
+
+      Ok(HandleResponse::default())
+    }
+    HandleMsg::Raise { amount } => {
+      require_at_least_two_players(state)?;
+
+      if state.game_board.round != GameRound::Matching
+        && state.game_board.round != GameRound::Matching2
+      {
+        return Err(StdError::generic_err(CANT_RAISE_NOW));
+      }
+      if state.game_board.raises >= state.table.max_raises {
+        return Err(StdError::generic_err(NO_RAISES_LEFT));
+      }
 
+      let highest_bet = get_highest_bet(state);
+      let player = get_requesting_player(&deps, env.clone())?;
+
+      if player.folded {
+        return Err(StdError::generic_err(CANT_BET_IF_FOLDED));
+      }
+      require_players_turn(state, &player)?;
       if player.chips < amount {
         return Err(StdError::generic_err("Not enough chips"));
       }
 
       for i in 0..state.players.len() {
         if state.players[i].addr == env.message.sender {
+          let current_bet = if state.game_board.round == GameRound::Matching {
+            state.players[i].bet
+          } else {
+            state.players[i].bet2
+          };
+          // the amount covers the match and the raise on top of it
+          if amount < highest_bet - current_bet + state.table.smallest_raise() {
+            return Err(StdError::generic_err(RAISE_TOO_SMALL));
+          }
+          state.game_board.pool += amount;
           if state.game_board.round == GameRound::Matching {
-            if amount < (highest_bet - state.players[i].bet) {
-              return Err(StdError::generic_err(WRONG_MATCHING_AMOUNT));
-            }
-            state.game_board.pool += highest_bet - state.players[i].bet;
-            state.players[i].bet = highest_bet;
+            state.players[i].bet += amount;
           } else {
-            if amount < (highest_bet - state.players[i].bet2) {
-              return Err(StdError::generic_err(WRONG_MATCHING_AMOUNT));
-            }
-            state.game_board.pool += highest_bet - state.players[i].bet2;
-            state.players[i].bet2 = highest_bet;
+            state.players[i].bet2 += amount;
           }
           state.players[i].chips -= amount;
-          state.players[i].last_action = Some(PlayerAction::MatchedBet);
+          let action = if state.players[i].chips == 0 {
+            PlayerAction::AllIn(amount)
+          } else {
+            PlayerAction::Raised(amount)
+          };
+          record_action(state, i, action);
         }
       }
+      state.game_board.raises += 1;
 
-      advance_turn_if_necessary(&mut state);
+      // everyone else is now behind the highest bet, so the round stays open until they've
+      // matched the raise or folded
+      advance_turn_if_necessary(state, &env);
 
-      deps
-        .storage
-        .set(b"state", &serde_json::to_vec(&state).unwrap());
+      save_state(&mut deps.storage, state)?;
 
       Ok(HandleResponse::default())
     }
     HandleMsg::Fold {} => {
-      require_at_least_two_players(&mut state)?;
+      require_at_least_two_players(state)?;
+      let player = get_requesting_player(&deps, env.clone())?;
+      require_players_turn(state, &player)?;
+      if !is_deck_dealt(state) {
+        return Err(StdError::generic_err(NO_CARDS_DEALT_YET));
+      }
+      if state.game_board.hand_log.is_settled() {
+        return Err(StdError::generic_err(HAND_IS_OVER));
+      }
+      // a word that's down is already in the showdown
+      if state
+        .game_board
+        .words
+        .iter()
+        .any(|w| w.player_addr == player.addr)
+      {
+        return Err(StdError::generic_err(CANT_FOLD_AFTER_PUTTING_DOWN));
+      }
 
       for i in 0..state.players.len() {
         if state.players[i].addr == env.message.sender {
-          state.players[i].folded = true;
-          state.players[i].hand = get_n_cards(&mut state, 5);
-          state.players[i].last_action = Some(PlayerAction::Folded);
+          fold_player(state, i, &env)?;
         }
       }
 
-      advance_turn_if_necessary(&mut state);
-      let messages = advance_to_next_turn_if_all_players_but_one_folded(&mut state)?;
+      advance_turn_if_necessary(state, &env);
+      let mut messages = advance_to_next_turn_if_all_players_but_one_folded(state, &env)?;
+      // everyone else may already have put down their word
+      if state.game_board.round == GameRound::Choice && !state.game_board.hand_log.is_settled() {
+        messages.append(&mut resolve_showdown_if_all_words_are_down(state, &env)?);
+      }
 
-      deps
-        .storage
-        .set(b"state", &serde_json::to_vec(&state).unwrap());
+      save_state(&mut deps.storage, state)?;
 
       send_messages_if_any(messages)
     }
     HandleMsg::Check {} => {
-      require_at_least_two_players(&mut state)?;
+      require_at_least_two_players(state)?;
+      let player = get_requesting_player(&deps, env.clone())?;
+      require_players_turn(state, &player)?;
 
       match state.game_board.round {
         GameRound::Blind | GameRound::Flop => {
@@ -554,43 +892,150 @@ pub fn handle<S: Storage, A: Api, Q: Querier>(
               } else {
                 state.players[i].checked2 = true;
               }
-              state.players[i].last_action = Some(PlayerAction::Checked);
+              record_action(state, i, PlayerAction::Checked);
             }
           }
         }
         _ => return Err(StdError::generic_err(CANT_CHECK_IF_NEED_TO_MATCH)),
       }
 
-      advance_turn_if_necessary(&mut state);
-      let messages = advance_to_next_turn_if_all_players_but_one_folded(&mut state)?;
-      deps
-        .storage
-        .set(b"state", &serde_json::to_vec(&state).unwrap());
+      advance_turn_if_necessary(state, &env);
+      let messages = advance_to_next_turn_if_all_players_but_one_folded(state, &env)?;
+      save_state(&mut deps.storage, state)?;
 
       send_messages_if_any(messages)
     }
-    HandleMsg::Leave {} => {
-      let mut chips: u64 = 0;
-      for i in 0..state.players.len() {
-        if state.players[i].addr == env.message.sender {
-          chips = state.players[i].chips;
-          state.players.remove(i);
-          break;
+    HandleMsg::ClaimTimeout {} => {
+      require_at_least_two_players(state)?;
+      get_requesting_player(&deps, env.clone())?;
+
+      if state.game_board.hand_log.is_settled() {
+        return Err(StdError::generic_err(NO_TIMEOUT_TO_CLAIM));
+      }
+      match state.game_board.round_deadline {
+        Some(deadline) if env.block.time > deadline => {}
+        _ => return Err(StdError::generic_err(NO_TIMEOUT_TO_CLAIM)),
+      }
+
+      if state.game_board.round == GameRound::Reveal {
+        let mut messages: Vec<CosmosMsg> = vec![];
+        let mut i = 0;
+        while i < state.players.len() {
+          if !is_ready(&state.players[i]) {
+            messages.append(&mut remove_player_who_isnt_ready(state, i, &env)?);
+          } else {
+            i += 1;
+          }
         }
+        messages.append(&mut deal_first_hand_if_everyone_is_ready(state, &env)?);
+
+        save_state(&mut deps.storage, state)?;
+        return send_messages_if_any(messages);
       }
 
-      advance_turn_if_necessary(&mut state);
-      let mut messages = advance_to_next_turn_if_all_players_but_one_folded(&mut state)?;
+      if state.game_board.round == GameRound::Choice {
+        // words are put down by everyone at once, so whoever hasn't is folded
+        for i in 0..state.players.len() {
+          let addr = state.players[i].addr.clone();
+          if is_in_hand(&state.players[i])
+            && !state.game_board.words.iter().any(|w| w.player_addr == addr)
+          {
+            fold_player(state, i, &env)?;
+          }
+        }
+      } else if let Some(addr) = state.game_board.action_on.clone() {
+        // only the player the action is on can be timed out
+        let i = state.players.iter().position(|p| p.addr == addr).unwrap();
+        match state.game_board.round {
+          GameRound::Blind | GameRound::Flop => {
+            // idle players are checked for free when nothing has to be matched
+            if state.game_board.round == GameRound::Blind {
+              state.players[i].checked = true;
+            } else {
+              state.players[i].checked2 = true;
+            }
+            record_action(state, i, PlayerAction::Checked);
+          }
+          GameRound::Matching | GameRound::Matching2 => fold_player(state, i, &env)?,
+          GameRound::None | GameRound::Reveal | GameRound::Choice => {}
+        }
+      }
+
+      advance_turn_if_necessary(state, &env);
+      let mut messages = advance_to_next_turn_if_all_players_but_one_folded(state, &env)?;
+      if state.game_board.round == GameRound::Choice && !state.game_board.hand_log.is_settled() {
+        messages.append(&mut resolve_showdown_if_all_words_are_down(state, &env)?);
+      }
+
+      save_state(&mut deps.storage, state)?;
+
+      send_messages_if_any(messages)
+    }
+    HandleMsg::CashOut { amount } => {
+      get_requesting_player(&deps, env.clone())?;
+      if !is_between_hands(state) {
+        return Err(StdError::generic_err(CANT_CASH_OUT_MID_HAND));
+      }
+      let index = state
+        .players
+        .iter()
+        .position(|p| p.addr == env.message.sender)
+        .unwrap();
+      if amount == 0 || amount > state.players[index].chips {
+        return Err(StdError::generic_err(INVALID_CASH_OUT));
+      }
+      state.players[index].chips -= amount;
+      let messages = vec![send_chips(state, &env, &env.message.sender, amount)?];
+      save_state(&mut deps.storage, state)?;
+
+      send_messages_if_any(messages)
+    }
+    HandleMsg::Leave {} => {
+      get_requesting_player(&deps, env.clone())?;
+      let index = state
+        .players
+        .iter()
+        .position(|p| p.addr == env.message.sender)
+        .unwrap();
+      let chips = state.players[index].chips;
+      let hand = state.players[index].hand.clone();
+      discard(state, hand);
+
+      // players waiting for the next hand have nothing at stake in this one
+      let waiting = state.players[index].waiting;
+      let mut messages: Vec<CosmosMsg> = vec![];
+      if is_between_hands(state) || waiting {
+        state.players.remove(index);
+        let addr = env.message.sender.clone();
+        state.game_board.join_queue.retain(|a| a != &addr);
+        if !is_deck_dealt(state) {
+          messages = deal_first_hand_if_everyone_is_ready(state, &env)?;
+        }
+      } else {
+        // leaving folds the hand, the bets stay in the pot and the seat is kept until it's over
+        state.players[index].hand = vec![];
+        state.players[index].chips = 0;
+        state.players[index].folded = true;
+        record_action(state, index, PlayerAction::Folded);
+        state.players[index].left = true;
+        let addr = env.message.sender.clone();
+        state.game_board.words.retain(|w| w.player_addr != addr);
+        advance_turn_if_necessary(state, &env);
+        messages = advance_to_next_turn_if_all_players_but_one_folded(state, &env)?;
+        if state.game_board.round == GameRound::Choice && !state.game_board.hand_log.is_settled() {
+          messages.append(&mut resolve_showdown_if_all_words_are_down(state, &env)?);
+        }
+      }
       if chips > 0 {
-        messages.push(CosmosMsg::Bank(BankMsg::Send {
-          from_address: env.contract.address.clone(),
-          to_address: env.message.sender.clone(),
-          amount: vec![Coin::new(chips as u128, "uscrt")],
-        }));
+        state.game_board.pending_withdrawals.push(Withdrawal {
+          addr: env.message.sender.clone(),
+          amount: chips,
+        });
+      }
+      if is_between_hands(state) || waiting || state.players.is_empty() {
+        messages.append(&mut settle_pending_withdrawals(state, &env)?);
       }
-      deps
-        .storage
-        .set(b"state", &serde_json::to_vec(&state).unwrap());
+      save_state(&mut deps.storage, state)?;
 
       send_messages_if_any(messages)
     }
@@ -609,26 +1054,253 @@ fn send_messages_if_any(messages: Vec<CosmosMsg>) -> Result<HandleResponse, StdE
   }
 }
 
+// `scores` holds the index and word rank of every player still contending for the pool, each
+// pot is split between the best ranked players eligible for it
 fn give_winners_their_money(
-  mut state: &mut State,
-  winners: Vec<usize>,
+  state: &mut State,
+  scores: Vec<(usize, (bool, u16))>,
+  env: &Env,
+) -> Result<Vec<CosmosMsg>, StdError> {
+  return_uncalled_bet(state);
+  update_pots(state);
+  let mut paid_out = 0;
+  for pot in state.game_board.pots.clone() {
+    let mut best_score = (false, 0);
+    let mut winners: Vec<usize> = vec![];
+    for (index, score) in scores.iter() {
+      if !pot.eligible.contains(&state.players[*index].addr) {
+        continue;
+      }
+      if winners.is_empty() || *score > best_score {
+        best_score = *score;
+        winners = vec![*index];
+      } else if *score == best_score {
+        winners.push(*index);
+      }
+    }
+    if winners.is_empty() {
+      continue;
+    }
+    if !state.game_board.hand_log.is_settled() {
+      state.game_board.hand_log.outcome =
+        Some(HandOutcome::Won(state.players[winners[0]].addr.clone()));
+    }
+
+    let pot_rake = pot.amount * state.table.rake_percentage / 100;
+    let amount_per_transfer = (pot.amount - pot_rake) / (winners.len() as u64);
+    for winner in winners.iter() {
+      state.players[*winner].chips += amount_per_transfer;
+      paid_out += amount_per_transfer;
+      let addr = state.players[*winner].addr.clone();
+      let payouts = &mut state.game_board.hand_log.payouts;
+      match payouts.iter_mut().find(|p| p.addr == addr) {
+        Some(payout) => payout.amount += amount_per_transfer,
+        None => payouts.push(Payout {
+          addr,
+          amount: amount_per_transfer,
+        }),
+      }
+    }
+  }
+  if !state.game_board.hand_log.is_settled() {
+    state.game_board.hand_log.outcome =
+      Some(HandOutcome::Won(state.players[scores[0].0].addr.clone()));
+  }
+
+  state.game_board.action_on = None;
+
+  // the bets are settled, nothing of them may be paid out again
+  for i in 0..state.players.len() {
+    state.players[i].bet = 0;
+    state.players[i].bet2 = 0;
+  }
+
+  // the rake and whatever couldn't be split evenly go to the jackpot
+  let mut messages: Vec<CosmosMsg> = vec![];
+  let rake = state.game_board.pool - paid_out;
+  state.game_board.hand_log.rake = rake;
+  state.game_board.pool = 0;
+  // a transfer of nothing would be rejected and revert the whole hand
+  if rake > 0 {
+    messages.push(fund_jackpot(state, rake)?);
+  }
+  messages.append(&mut settle_pending_withdrawals(state, env)?);
+  Ok(messages)
+}
+
+// what the biggest bettor put in beyond everyone else was never called, so it goes back to them
+// before any rake is taken
+fn return_uncalled_bet(state: &mut State) {
+  let contributions: Vec<u64> = state.players.iter().map(|p| p.bet + p.bet2).collect();
+  let top = match (0..contributions.len()).max_by_key(|&i| contributions[i]) {
+    Some(top) => top,
+    None => return,
+  };
+  let called = contributions
+    .iter()
+    .enumerate()
+    .filter(|&(i, _)| i != top)
+    .map(|(_, &c)| c)
+    .max()
+    .unwrap_or(0);
+  let uncalled = contributions[top] - called;
+  // a folded player's bets stay in the pot
+  if uncalled == 0 || state.players[top].folded {
+    return;
+  }
+
+  let player = &mut state.players[top];
+  let from_bet2 = uncalled.min(player.bet2);
+  player.bet2 -= from_bet2;
+  player.bet -= uncalled - from_bet2;
+  player.chips += uncalled;
+  state.game_board.pool -= uncalled;
+  let addr = player.addr.clone();
+  state.game_board.hand_log.payouts.push(Payout {
+    addr,
+    amount: uncalled,
+  });
+}
+
+fn settle_pending_withdrawals(state: &mut State, env: &Env) -> StdResult<Vec<CosmosMsg>> {
+  let mut messages: Vec<CosmosMsg> = vec![];
+  for withdrawal in state.game_board.pending_withdrawals.clone() {
+    messages.push(send_chips(state, env, &withdrawal.addr, withdrawal.amount)?);
+  }
+  state.game_board.pending_withdrawals = vec![];
+  Ok(messages)
+}
+
+fn resolve_showdown_if_all_words_are_down(
+  state: &mut State,
+  env: &Env,
 ) -> Result<Vec<CosmosMsg>, StdError> {
-  state.game_board.winner_for_turn = Some(state.players[winners[0]].addr.clone());
-  let rake = state.game_board.pool * state.game_board.rake_percentage / 100;
-  let amount_per_transfer = (state.game_board.pool - rake) / (winners.len() as u64);
-  if state.game_board.pool > 0 {
-    for i in 0..winners.len() {
-      state.players[winners[i]].chips += amount_per_transfer;
+  let non_folded_players = get_non_folded_players(state);
+  if state.game_board.words.len() != non_folded_players.len() {
+    return Ok(vec![]);
+  }
+  if state.game_board.words.is_empty() {
+    return end_hand_without_showdown(state, env);
+  }
+
+  let winners = get_winners_for_turn(state);
+
+  let mut scores: Vec<(usize, (bool, u16))> = vec![];
+  for word in state.game_board.words.iter() {
+    for j in 0..state.players.len() {
+      if state.players[j].addr == word.player_addr {
+        scores.push((j, get_rank_for_played_word(word, &state.dictionary)));
+        break;
+      }
+    }
+  }
+
+  for word in state.game_board.words.clone() {
+    state.game_board.hand_log.words.push(ScoredWord {
+      points: get_score_for_played_word(&word, &state.dictionary),
+      player_addr: word.player_addr,
+      cards: word.cards,
+    });
+  }
+  let mut messages = give_winners_their_money(state, scores, env)?;
+
+  deal_damage_to_losers(state, &winners);
+  messages.append(&mut eliminate_players_without_hp(state, env)?);
+  Ok(messages)
+}
+
+fn deal_damage_to_losers(state: &mut State, winners: &[Word]) {
+  let winning_score = get_score_for_played_word(&winners[0], &state.dictionary);
+  for word in state.game_board.words.clone() {
+    if winners.iter().any(|w| w.player_addr == word.player_addr) {
+      continue;
+    }
+    let score_gap = winning_score - get_score_for_played_word(&word, &state.dictionary);
+    // a zero divisor means the damage doesn't scale with the score gap
+    let damage = state.table.damage.base as u16
+      + score_gap
+        .checked_div(state.table.damage.points_per_extra_damage)
+        .unwrap_or(0);
+    for i in 0..state.players.len() {
+      if state.players[i].addr == word.player_addr {
+        state.players[i].hp = state.players[i].hp.saturating_sub(damage.min(255) as u8);
+        break;
+      }
+    }
+  }
+}
+
+fn eliminate_players_without_hp(state: &mut State, env: &Env) -> StdResult<Vec<CosmosMsg>> {
+  let mut messages: Vec<CosmosMsg> = vec![];
+  for i in 0..state.players.len() {
+    if state.players[i].hp == 0 {
+      state.players[i].folded = true;
+      state.players[i].last_action = Some(PlayerAction::Folded);
+      if let Some(msg) = cash_out_chips(state, i, env)? {
+        messages.push(msg);
+      }
+    }
+  }
+
+  // players who left are out of the game whatever hp they had
+  let standing: Vec<usize> = (0..state.players.len())
+    .filter(|&i| state.players[i].hp > 0 && !state.players[i].left)
+    .collect();
+  if standing.len() == 1 {
+    // last player standing wins the game and takes their chips home
+    state.winner = Some(state.players[standing[0]].addr.clone());
+    if let Some(msg) = cash_out_chips(state, standing[0], env)? {
+      messages.push(msg);
+    }
+  }
+  Ok(messages)
+}
+
+fn cash_out_chips(state: &mut State, index: usize, env: &Env) -> StdResult<Option<CosmosMsg>> {
+  let chips = state.players[index].chips;
+  if chips == 0 {
+    return Ok(None);
+  }
+  state.players[index].chips = 0;
+  let to = state.players[index].addr.clone();
+  Ok(Some(send_chips(state, env, &to, chips)?))
+}
+
+fn buy_chips(
+  state: &mut State,
+  buyer: &HumanAddr,
+  amount: u64,
+  env: &Env,
+) -> StdResult<Vec<CosmosMsg>> {
+  let between_hands = is_between_hands(state);
+  for i in 0..state.players.len() {
+    if &state.players[i].addr == buyer {
+      // chips already in the pot can't be topped up, the pots would no longer add up
+      if !between_hands && state.players[i].bet + state.players[i].bet2 > 0 {
+        return Err(StdError::generic_err(CANT_BUY_MID_HAND));
+      }
+      // top-ups only have to bring the stack up to the minimum buy
+      if amount == 0 || state.players[i].chips + amount < state.min_buy {
+        return Err(StdError::generic_err(
+          "You didn't send enough to this function or you sent too much",
+        ));
+      }
+      if state.players[i].chips + amount > state.max_buy {
+        return Err(StdError::generic_err(
+          "You can't buy more chips than the maximum amount",
+        ));
+      }
+      state.players[i].chips += amount;
     }
-    state.game_board.pool = 0;
-    let msg = SecretDreamscapeJackpot::Fund {}.to_cosmos_msg(
-      state.jackpot_hash.clone(),
-      state.jackpot_addr.clone(),
-      Some(Uint128(rake as u128)),
-    )?;
-    return Ok(vec![msg]);
   }
-  return Ok(vec![]);
+  if !is_deck_dealt(state) {
+    return deal_first_hand_if_everyone_is_ready(state, env);
+  }
+  // players without chips are passed over, so the one who just bought in may be next
+  if !between_hands {
+    move_action_on(state, env);
+  }
+  Ok(vec![])
 }
 
 fn get_highest_bet(state: &State) -> u64 {
@@ -647,16 +1319,46 @@ fn get_highest_bet(state: &State) -> u64 {
 
 fn advance_to_next_turn_if_all_players_but_one_folded(
   state: &mut State,
+  env: &Env,
 ) -> Result<Vec<CosmosMsg>, StdError> {
   let non_folded = get_non_folded_players(state);
+  if state.game_board.hand_log.is_settled() {
+    return Ok(vec![]);
+  }
+  if non_folded.is_empty() {
+    return end_hand_without_showdown(state, env);
+  }
   if non_folded.len() == 1 {
     let winner = non_folded[0].clone().addr;
     let non_folded_index = state.players.iter().position(|p| p.addr == winner).unwrap();
-    return give_winners_their_money(state, vec![non_folded_index]);
+    return give_winners_their_money(state, vec![(non_folded_index, (false, 0))], env);
   }
   Ok(vec![])
 }
 
+// nobody is left to win the pool, so everyone gets back what they bet and the hand is handed
+// to whoever acted last, which lets the next one be requested
+fn end_hand_without_showdown(state: &mut State, env: &Env) -> StdResult<Vec<CosmosMsg>> {
+  for player in state.players.iter_mut() {
+    let refund = player.bet + player.bet2;
+    player.bet = 0;
+    player.bet2 = 0;
+    if refund == 0 {
+      continue;
+    }
+    player.chips += refund;
+    state.game_board.hand_log.payouts.push(Payout {
+      addr: player.addr.clone(),
+      amount: refund,
+    });
+  }
+  state.game_board.pool = 0;
+  state.game_board.pots = vec![];
+  state.game_board.action_on = None;
+  state.game_board.hand_log.outcome = Some(HandOutcome::Refunded);
+  settle_pending_withdrawals(state, env)
+}
+
 fn get_bet_stats(state: &mut State) -> (bool, bool, bool, bool, bool) {
   let mut last_bet = 0;
   let mut all_non_folded_players_bet = true;
@@ -666,21 +1368,24 @@ fn get_bet_stats(state: &mut State) -> (bool, bool, bool, bool, bool) {
     GameRound::Flop => p.checked2,
     _ => false,
   });
-  let all_non_folded_players_checked =
-    state
-      .players
-      .iter()
-      .filter(|p| !p.folded)
-      .all(|p| match state.game_board.round {
-        GameRound::Blind => p.checked,
-        GameRound::Flop => p.checked2,
-        _ => false,
-      });
-  let all_players_acted = state.players.iter().all(|p| p.last_action.is_some());
+  let all_non_folded_players_checked = state
+    .players
+    .iter()
+    .filter(|p| is_in_hand(p) && !is_all_in(p))
+    .all(|p| match state.game_board.round {
+      GameRound::Blind => p.checked,
+      GameRound::Flop => p.checked2,
+      _ => false,
+    });
+  let all_players_acted = state
+    .players
+    .iter()
+    .all(|p| p.waiting || p.last_action.is_some());
   for i in 0..state.players.len() {
-    if !state.players[i].folded {
+    // all-in players put in everything they could, so they don't hold up the betting
+    if is_in_hand(&state.players[i]) && !is_all_in(&state.players[i]) {
       let player_bet = match state.game_board.round {
-        GameRound::None => 0,
+        GameRound::None | GameRound::Reveal => 0,
         GameRound::Blind | GameRound::Matching => state.players[i].bet,
         GameRound::Flop | GameRound::Matching2 => state.players[i].bet2,
         GameRound::Choice => state.players[i].bet + state.players[i].bet2,
@@ -712,7 +1417,327 @@ fn get_bet_stats(state: &mut State) -> (bool, bool, bool, bool, bool) {
   )
 }
 
-fn advance_turn_if_necessary(state: &mut State) {
+fn validate_merkle_root(root: &Binary) -> StdResult<()> {
+  if root.len() != 32 {
+    return Err(StdError::generic_err(INVALID_MERKLE_ROOT));
+  }
+  Ok(())
+}
+
+fn validate_dictionary(dictionary: &Dictionary) -> StdResult<()> {
+  match &dictionary.merkle_root {
+    Some(root) => validate_merkle_root(root)?,
+    None if !cfg!(feature = "word-lists") => return Err(StdError::generic_err(NO_WORD_LIST)),
+    None => {}
+  }
+  let letters = alphabet(&dictionary.word_list).len();
+  if dictionary.points.len() != letters {
+    return Err(StdError::generic_err(INVALID_LETTER_POINTS));
+  }
+  if dictionary.cards_in_deck() == 0
+    || dictionary
+      .distribution
+      .iter()
+      .any(|(letter, _)| *letter as usize >= letters)
+  {
+    return Err(StdError::generic_err(INVALID_DISTRIBUTION));
+  }
+  Ok(())
+}
+
+fn validate_table_config(table: &TableConfig, cards_in_deck: usize) -> StdResult<()> {
+  if table.max_players < 2 {
+    return Err(StdError::generic_err(INVALID_MAX_PLAYERS));
+  }
+  if table.starting_hp == 0 {
+    return Err(StdError::generic_err(INVALID_STARTING_HP));
+  }
+  // hand indexes from 250 up point at the river
+  if table.hand_size == 0 || table.hand_size >= 250 {
+    return Err(StdError::generic_err(INVALID_HAND_SIZE));
+  }
+  if table.river_size > 6 {
+    return Err(StdError::generic_err(INVALID_RIVER_SIZE));
+  }
+  let cards_in_play =
+    table.max_players as usize * table.hand_size as usize + table.river_size as usize;
+  if cards_in_play > cards_in_deck {
+    return Err(StdError::generic_err(NOT_ENOUGH_CARDS));
+  }
+  if table.gold_cards as usize > cards_in_deck {
+    return Err(StdError::generic_err(INVALID_GOLD_CARDS));
+  }
+  if table.rake_percentage > 100 {
+    return Err(StdError::generic_err(INVALID_RAKE));
+  }
+  if table.min_bet == 0 {
+    return Err(StdError::generic_err(INVALID_MIN_BET));
+  }
+  if table.big_blind < table.small_blind {
+    return Err(StdError::generic_err(INVALID_BLINDS));
+  }
+  if table.damage.base == 0 {
+    return Err(StdError::generic_err(INVALID_DAMAGE));
+  }
+  if table.turn_timeout == 0 {
+    return Err(StdError::generic_err(INVALID_TURN_TIMEOUT));
+  }
+  if table.min_raise == Some(0) {
+    return Err(StdError::generic_err(INVALID_MIN_RAISE));
+  }
+  Ok(())
+}
+
+fn is_deck_dealt(state: &State) -> bool {
+  state.game_board.round != GameRound::None && state.game_board.round != GameRound::Reveal
+}
+
+// no chips are at stake before the first deal or once the hand is settled
+fn is_between_hands(state: &State) -> bool {
+  !is_deck_dealt(state) || state.game_board.hand_log.is_settled()
+}
+
+// seated players have to reveal their secret and buy in before the first hand is dealt
+fn is_ready(player: &Player) -> bool {
+  player.revealed_secret.is_some() && player.chips > 0
+}
+
+fn deal_first_hand_if_everyone_is_ready(state: &mut State, env: &Env) -> StdResult<Vec<CosmosMsg>> {
+  if state.players.len() < 2 {
+    state.game_board.round = GameRound::None;
+    state.game_board.round_deadline = None;
+    return Ok(vec![]);
+  }
+  if state.game_board.round == GameRound::None {
+    state.game_board.round = GameRound::Reveal;
+    reset_round_deadline(state, env);
+  }
+  if !state.players.iter().all(is_ready) {
+    return Ok(vec![]);
+  }
+
+  // every seated player committed to their secret before anyone revealed, so no single
+  // player can steer the shuffle
+  state.deck = generate_deck(get_rng(state), state.table.gold_cards, &state.dictionary);
+  state.game_board.river = get_n_cards(state, state.table.river_size, env)?;
+  for i in 0..state.players.len() {
+    state.players[i].hand = get_n_cards(state, state.table.hand_size, env)?;
+  }
+  state.game_board.round = GameRound::Blind;
+  reset_round_deadline(state, env);
+  // the button starts on the last seat, so the first seat posts the small blind
+  state.game_board.dealer = state.players[state.players.len() - 1].seat;
+  start_hand(state, env)
+}
+
+// logs the hand that was just dealt and takes the forced bets
+fn start_hand(state: &mut State, env: &Env) -> StdResult<Vec<CosmosMsg>> {
+  start_hand_log(state);
+  post_forced_bets(state);
+  // the blinds may already settle the first round, otherwise the action starts after them
+  state.game_board.action_on = None;
+  advance_turn_if_necessary(state, env);
+  // everyone else may have been sat out
+  advance_to_next_turn_if_all_players_but_one_folded(state, env)
+}
+
+// passes the action on once the player it's on has nothing left to do this round, the next
+// player gets the full timeout to act
+fn move_action_on(state: &mut State, env: &Env) {
+  let seat = match &state.game_board.action_on {
+    Some(addr) => match state.players.iter().position(|p| &p.addr == addr) {
+      Some(index) if needs_to_act(state, index) => return,
+      Some(index) => state.players[index].seat,
+      None => state.game_board.dealer,
+    },
+    None => state.game_board.dealer,
+  };
+  let next = next_to_act(state, seat);
+  if next != state.game_board.action_on {
+    reset_round_deadline(state, env);
+  }
+  state.game_board.action_on = next;
+}
+
+// first player after the given seat, going around the table, who still has to act
+fn next_to_act(state: &State, seat: u8) -> Option<HumanAddr> {
+  let after = (0..state.players.len()).filter(|&i| state.players[i].seat > seat);
+  let before = (0..state.players.len()).filter(|&i| state.players[i].seat <= seat);
+  for i in after.chain(before) {
+    if needs_to_act(state, i) {
+      return Some(state.players[i].addr.clone());
+    }
+  }
+  None
+}
+
+fn needs_to_act(state: &State, index: usize) -> bool {
+  let player = &state.players[index];
+  if !is_in_hand(player) || is_all_in(player) || state.game_board.hand_log.is_settled() {
+    return false;
+  }
+  match state.game_board.round {
+    GameRound::Blind | GameRound::Flop => player.last_action.is_none(),
+    GameRound::Matching => player.bet < get_highest_bet(state),
+    GameRound::Matching2 => player.bet2 < get_highest_bet(state),
+    // words are put down by everyone at once
+    GameRound::None | GameRound::Reveal | GameRound::Choice => false,
+  }
+}
+
+fn move_dealer_button(state: &mut State) {
+  let dealer = state.game_board.dealer;
+  let standing = |p: &&Player| p.hp > 0;
+  state.game_board.dealer = match state
+    .players
+    .iter()
+    .filter(standing)
+    .find(|p| p.seat > dealer)
+  {
+    Some(player) => player.seat,
+    None => match state.players.iter().find(standing) {
+      Some(player) => player.seat,
+      None => dealer,
+    },
+  };
+}
+
+// takes the ante from everyone dealt in, then the small and big blind from the next players
+// after the dealer. Whoever can't cover what they owe sits out the hand
+fn post_forced_bets(state: &mut State) {
+  let ante = state.table.ante;
+  if ante > 0 {
+    for i in 0..state.players.len() {
+      if state.players[i].folded || state.players[i].hp == 0 {
+        continue;
+      }
+      if state.players[i].chips < ante {
+        sit_out(state, i);
+        continue;
+      }
+      put_in_forced_bet(state, i, ante);
+    }
+  }
+
+  let mut seat = state.game_board.dealer;
+  let blinds = [state.table.small_blind, state.table.big_blind];
+  for blind in blinds.iter() {
+    if *blind == 0 {
+      continue;
+    }
+    while let Some(i) = next_to_post_blind(state, seat) {
+      seat = state.players[i].seat;
+      if state.players[i].chips < *blind {
+        sit_out(state, i);
+        continue;
+      }
+      put_in_forced_bet(state, i, *blind);
+      let action = if state.players[i].chips == 0 {
+        PlayerAction::AllIn(*blind)
+      } else {
+        PlayerAction::PostedBlind(*blind)
+      };
+      record_action(state, i, action);
+      break;
+    }
+  }
+}
+
+// first player after the given seat who is dealt in and hasn't posted a blind yet
+fn next_to_post_blind(state: &State, seat: u8) -> Option<usize> {
+  let after = (0..state.players.len()).filter(|&i| state.players[i].seat > seat);
+  let before = (0..state.players.len()).filter(|&i| state.players[i].seat <= seat);
+  for i in after.chain(before) {
+    let player = &state.players[i];
+    if !player.folded && player.hp > 0 && player.last_action.is_none() {
+      return Some(i);
+    }
+  }
+  None
+}
+
+fn put_in_forced_bet(state: &mut State, index: usize, amount: u64) {
+  state.players[index].chips -= amount;
+  state.players[index].bet += amount;
+  state.game_board.pool += amount;
+}
+
+// keeps a player out of the hand without discarding their cards
+fn sit_out(state: &mut State, index: usize) {
+  state.players[index].folded = true;
+  record_action(state, index, PlayerAction::Folded);
+}
+
+// sets what the player did last and adds it to the log of the hand
+fn record_action(state: &mut State, index: usize, action: PlayerAction) {
+  state.game_board.hand_log.actions.push(LoggedAction {
+    addr: state.players[index].addr.clone(),
+    round: state.game_board.round.clone(),
+    action: action.clone(),
+  });
+  state.players[index].last_action = Some(action);
+}
+
+// starts a fresh log for the hand that was just dealt
+fn start_hand_log(state: &mut State) {
+  let mut hands = vec![];
+  for player in state.players.iter() {
+    if is_in_hand(player) {
+      hands.push(DealtHand {
+        addr: player.addr.clone(),
+        cards: player.hand.clone(),
+      });
+    }
+  }
+  state.game_board.hand_log = HandRecord {
+    turn: state.game_board.turn,
+    hands,
+    ..HandRecord::default()
+  };
+}
+
+fn require_players_turn(state: &State, player: &Player) -> StdResult<()> {
+  if player.waiting {
+    return Err(StdError::generic_err(WAITING_FOR_NEXT_HAND));
+  }
+  match &state.game_board.action_on {
+    Some(addr) if addr != &player.addr => Err(StdError::generic_err(NOT_YOUR_TURN)),
+    _ => Ok(()),
+  }
+}
+
+fn remove_player_who_isnt_ready(
+  state: &mut State,
+  index: usize,
+  env: &Env,
+) -> Result<Vec<CosmosMsg>, StdError> {
+  let mut messages: Vec<CosmosMsg> = vec![];
+  let penalty = state.players[index].chips.min(state.min_buy);
+  if penalty > 0 {
+    state.players[index].chips -= penalty;
+    messages.push(fund_jackpot(state, penalty)?);
+  }
+  if let Some(msg) = cash_out_chips(state, index, env)? {
+    messages.push(msg);
+  }
+  state.players.remove(index);
+  Ok(messages)
+}
+
+fn fold_player(state: &mut State, index: usize, env: &Env) -> StdResult<()> {
+  state.players[index].folded = true;
+  let folded_hand = state.players[index].hand.clone();
+  discard(state, folded_hand);
+  state.players[index].hand = get_n_cards(state, state.table.hand_size, env)?;
+  record_action(state, index, PlayerAction::Folded);
+  Ok(())
+}
+
+fn reset_round_deadline(state: &mut State, env: &Env) {
+  state.game_board.round_deadline = Some(env.block.time + state.table.turn_timeout);
+}
+
+fn advance_turn_if_necessary(state: &mut State, env: &Env) {
   let previous_round = state.game_board.round.clone();
   let (
     all_players_acted,
@@ -725,6 +1750,7 @@ fn advance_turn_if_necessary(state: &mut State) {
     && state.game_board.round != GameRound::Matching2
     && !all_players_acted
   {
+    move_action_on(state, env);
     return;
   }
   if all_non_folded_players_checked {
@@ -743,7 +1769,7 @@ fn advance_turn_if_necessary(state: &mut State) {
     }
   } else {
     match state.game_board.round {
-      GameRound::None => {}
+      GameRound::None | GameRound::Reveal => {}
       GameRound::Blind => match (all_non_folded_players_bet, all_players_bet_the_same_amount) {
         (true, false) => state.game_board.round = GameRound::Matching,
         (true, true) => state.game_board.round = GameRound::Flop,
@@ -767,35 +1793,127 @@ fn advance_turn_if_necessary(state: &mut State) {
       GameRound::Choice => {}
     }
   }
+  if state.game_board.round == GameRound::Flop && previous_round != GameRound::Flop {
+    let players_who_can_bet = state
+      .players
+      .iter()
+      .filter(|p| is_in_hand(p) && !is_all_in(p))
+      .count();
+    if players_who_can_bet <= 1 {
+      // nobody is left to bet against, so the hand goes straight to the words
+      state.game_board.round = GameRound::Choice;
+    }
+  }
   if previous_round != state.game_board.round {
+    if is_river_shown(&state.game_board.round) && !is_river_shown(&previous_round) {
+      state.game_board.hand_log.river = state.game_board.river.clone();
+    }
+    reset_round_deadline(state, env);
+    state.game_board.raises = 0;
     for i in 0..state.players.len() {
-      if state.players[i].folded {
+      if state.players[i].folded || is_all_in(&state.players[i]) {
         continue;
       }
       state.players[i].last_action = None;
     }
+    // every round is opened by the first player after the dealer
+    let dealer = state.game_board.dealer;
+    state.game_board.action_on = next_to_act(state, dealer);
+  } else {
+    move_action_on(state, env);
   }
+  update_pots(state);
+}
+
+fn is_all_in(player: &Player) -> bool {
+  is_in_hand(player) && player.chips == 0
+}
+
+// splits the pool into a main pot and side pots, one per contribution level of the all-in players
+fn update_pots(state: &mut State) {
+  let contributions: Vec<u64> = state.players.iter().map(|p| p.bet + p.bet2).collect();
+  let mut levels: Vec<u64> = contributions.iter().filter(|&&c| c > 0).cloned().collect();
+  levels.sort_unstable();
+  levels.dedup();
+
+  let mut pots: Vec<Pot> = vec![];
+  let mut previous_level = 0;
+  for level in levels {
+    let mut amount = 0;
+    let mut eligible: Vec<HumanAddr> = vec![];
+    for (player, &contribution) in state.players.iter().zip(contributions.iter()) {
+      amount += contribution.min(level) - contribution.min(previous_level);
+      if !player.folded && contribution >= level {
+        eligible.push(player.addr.clone());
+      }
+    }
+    previous_level = level;
+    if eligible.is_empty() && pots.is_empty() {
+      // only folded players bet, whoever is still in the hand contends for it
+      eligible = get_non_folded_players(state)
+        .iter()
+        .map(|p| p.addr.clone())
+        .collect();
+    }
+
+    match pots.last_mut() {
+      // levels only folded players reached, or with the same contenders, belong to the pot below
+      Some(last) if eligible.is_empty() || last.eligible == eligible => last.amount += amount,
+      _ => pots.push(Pot { amount, eligible }),
+    }
+  }
+
+  // chips of players who left mid-hand stay in the main pot
+  let counted: u64 = pots.iter().map(|p| p.amount).sum();
+  let left_behind = state.game_board.pool.saturating_sub(counted);
+  if left_behind > 0 {
+    match pots.first_mut() {
+      Some(main_pot) => main_pot.amount += left_behind,
+      None => pots.push(Pot {
+        amount: left_behind,
+        eligible: get_non_folded_players(state)
+          .iter()
+          .map(|p| p.addr.clone())
+          .collect(),
+      }),
+    }
+  }
+  state.game_board.pots = pots;
 }
 
 fn get_winners_for_turn(state: &State) -> Vec<Word> {
-  let mut max_score = 0;
-  let mut highest_scoring_words: Vec<Word> = vec![];
+  let mut max_rank = (false, 0);
+  let mut highest_ranked_words: Vec<Word> = vec![];
 
   for i in 0..state.game_board.words.len() {
     let word = state.game_board.words[i].clone();
-    let score_for_word = get_score_for_word(&*word.cards);
-    match score_for_word.cmp(&max_score) {
+    let rank_for_word = get_rank_for_played_word(&word, &state.dictionary);
+    match rank_for_word.cmp(&max_rank) {
       Ordering::Equal => {
-        highest_scoring_words.push(word.clone());
+        highest_ranked_words.push(word.clone());
       }
       Ordering::Less => {}
       Ordering::Greater => {
-        max_score = score_for_word;
-        highest_scoring_words = vec![word.clone()];
+        max_rank = rank_for_word;
+        highest_ranked_words = vec![word.clone()];
       }
     }
   }
-  highest_scoring_words
+  highest_ranked_words
+}
+
+// seats everyone who joined during the last hand, their cards are dealt with the others'
+fn deal_in_join_queue(state: &mut State) {
+  let queue: Vec<HumanAddr> = state.game_board.join_queue.drain(..).collect();
+  for addr in queue {
+    if let Some(i) = state.players.iter().position(|p| p.addr == addr) {
+      state.players[i].waiting = false;
+    }
+  }
+}
+
+fn release_seats_of_players_who_left(state: &mut State) {
+  state.players.retain(|p| !p.left);
 }
 
 fn require_at_least_two_players(state: &mut State) -> StdResult<bool> {
@@ -809,18 +1927,8 @@ pub(crate) fn get_requesting_player<S: Storage, A: Api, Q: Querier>(
   deps: &&mut Extern<S, A, Q>,
   env: Env,
 ) -> Result<Player, StdError> {
-  let state: State = serde_json::from_slice(&deps.storage.get(b"state").unwrap()).unwrap();
-
-  for player in &state.players {
-    if player.addr == env.message.sender {
-      return Ok(player.clone());
-    }
+  match load_player(&deps.storage, &env.message.sender)? {
+    Some(player) if !player.left => Ok(player),
+    _ => Err(StdError::generic_err(NOT_IN_GAME)),
   }
-  Err(StdError::generic_err(NOT_IN_GAME))
-}
-
-#[derive(Serialize, Deserialize, Clone, JsonSchema)]
-#[serde(rename_all = "snake_case")]
-struct PlayerHand {
-  cards: Vec<Card>,
 }
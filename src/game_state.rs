@@ -1,7 +1,8 @@
-use cosmwasm_std::HumanAddr;
+use cosmwasm_std::{Binary, HumanAddr};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::constants::{DEFAULT_MAX_RAISES, DEFAULT_TURN_TIMEOUT};
 use crate::contract::SecretDreamscapeNFT;
 
 #[derive(Serialize, Deserialize, Clone, JsonSchema)]
@@ -12,6 +13,8 @@ pub struct State {
   pub game_board: GameBoard,
   pub winner: Option<HumanAddr>,
   pub deck: Vec<Card>,
+  // cards that left play, shuffled back into the deck when it runs low
+  pub discard_pile: Vec<Card>,
   pub started_time: u64,
   pub level_design: u64,
   pub password: Option<String>,
@@ -22,12 +25,215 @@ pub struct State {
   pub max_buy: u64,
   pub jackpot_addr: HumanAddr,
   pub jackpot_hash: String,
+  pub contract_addr: HumanAddr,
+  pub table: TableConfig,
+  pub currency: Currency,
+  pub dictionary: Dictionary,
+
+  // the only one who can change the table's dictionary root, tables migrated from before
+  // there was one don't have it
+  pub admin: Option<HumanAddr>,
+}
+
+// what chips are bought and paid out with
+#[derive(Serialize, Deserialize, Clone, JsonSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Currency {
+  Native { denom: String },
+  Snip20 { addr: HumanAddr, hash: String },
+}
+
+impl Default for Currency {
+  fn default() -> Self {
+    Currency::Native {
+      denom: "uscrt".to_string(),
+    }
+  }
+}
+
+#[derive(Serialize, Deserialize, Clone, JsonSchema, PartialEq)]
+pub struct TableConfig {
+  pub max_players: u8,
+  pub starting_hp: u8,
+  pub hand_size: u8,
+  pub river_size: u8,
+
+  // cards in the deck that double the score of a word
+  pub gold_cards: u8,
+
+  // percentage of every pot that goes to the jackpot
+  pub rake_percentage: u64,
+
+  // smallest bet in the table's currency, players with fewer chips sit out the next hand
+  pub min_bet: u64,
+
+  // forced bets taken at the start of every hand, the blinds are posted by the two players
+  // after the dealer and the ante by everyone dealt in
+  pub small_blind: u64,
+  pub big_blind: u64,
+  pub ante: u64,
+
+  // what happens to empty words and words that aren't in the dictionary
+  pub invalid_words: InvalidWordPolicy,
+
+  // hp the losers of a showdown lose
+  pub damage: DamageRule,
+
+  // seconds every player has to act before the round can be forced forward
+  pub turn_timeout: u64,
+
+  // smallest amount a raise adds on top of the bet it matches, the min bet when not set
+  pub min_raise: Option<u64>,
+
+  // raises allowed in each of the matching rounds
+  pub max_raises: u8,
+}
+
+#[derive(Serialize, Deserialize, Clone, JsonSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum InvalidWordPolicy {
+  // they can't be put down
+  Reject,
+  // they're put down for 0 points and lose to every word in the dictionary
+  AutoLoss,
+}
+
+impl Default for TableConfig {
+  fn default() -> Self {
+    TableConfig {
+      max_players: 4,
+      starting_hp: 5,
+      hand_size: 5,
+      river_size: 5,
+      gold_cards: 5,
+      rake_percentage: 10,
+      min_bet: 125_000,
+      small_blind: 0,
+      big_blind: 0,
+      ante: 0,
+      invalid_words: InvalidWordPolicy::AutoLoss,
+      damage: DamageRule::default(),
+      turn_timeout: DEFAULT_TURN_TIMEOUT,
+      min_raise: None,
+      max_raises: DEFAULT_MAX_RAISES,
+    }
+  }
+}
+
+impl TableConfig {
+  pub fn smallest_raise(&self) -> u64 {
+    self.min_raise.unwrap_or(self.min_bet)
+  }
+}
+
+#[derive(Serialize, Deserialize, Clone, JsonSchema, PartialEq)]
+pub struct DamageRule {
+  // hp every player that didn't win the showdown loses
+  pub base: u8,
+
+  // if non-zero, losers lose one extra hp for every `points_per_extra_damage` points
+  // their word scored below the winning word
+  pub points_per_extra_damage: u16,
+}
+
+impl Default for DamageRule {
+  fn default() -> Self {
+    DamageRule {
+      base: 1,
+      points_per_extra_damage: 0,
+    }
+  }
+}
+
+// word lists compiled into the contract by build.rs, the letter of a card is its index in
+// the list's alphabet. The alphabets are fixed when the contract is built, a table can pick
+// its points and distribution but not its letters
+#[derive(Serialize, Deserialize, Clone, JsonSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WordList {
+  English,
+}
+
+// the words a table plays with and what its letters are worth
+#[derive(Serialize, Deserialize, Clone, JsonSchema, PartialEq)]
+pub struct Dictionary {
+  // alphabet of the cards, and the words they can spell unless there's a merkle root
+  pub word_list: WordList,
+
+  // root of a merkle tree of the words, they're then proven when put down instead of looked
+  // up in the word list
+  pub merkle_root: Option<Binary>,
+
+  // points of every letter of the alphabet, in alphabet order
+  pub points: Vec<u16>,
+
+  // (letter, count) of the cards in the deck
+  pub distribution: Vec<(u8, u8)>,
+
+  // blank cards in the deck on top of the distribution, scrabble has 2
+  pub blanks: u8,
+}
+
+impl Default for Dictionary {
+  fn default() -> Self {
+    Dictionary {
+      word_list: WordList::English,
+      merkle_root: None,
+      points: vec![
+        1, 3, 3, 2, 1, 4, 2, 4, 1, 8, 5, 1, 3, 1, 1, 3, 10, 1, 1, 1, 1, 4, 4, 8, 4, 8,
+      ],
+      distribution: vec![
+        (4, 12),
+        (0, 9),
+        (8, 9),
+        (14, 8),
+        (13, 6),
+        (17, 6),
+        (19, 6),
+        (11, 4),
+        (18, 4),
+        (20, 4),
+        (3, 4),
+        (6, 3),
+        (1, 2),
+        (2, 2),
+        (12, 2),
+        (15, 2),
+        (5, 2),
+        (7, 2),
+        (21, 2),
+        (22, 2),
+        (24, 2),
+        (10, 1),
+        (9, 1),
+        (23, 1),
+        (16, 1),
+        (25, 1),
+      ],
+      blanks: 2,
+    }
+  }
+}
+
+impl Dictionary {
+  pub fn cards_in_deck(&self) -> usize {
+    self
+      .distribution
+      .iter()
+      .map(|(_, count)| *count as usize)
+      .sum()
+  }
 }
 
 #[derive(Serialize, Deserialize, Clone, JsonSchema, PartialEq)]
 pub enum PlayerAction {
   SentBet(u64),
+  // forced bet taken when the hand started, it stands in for the player's blind round action
+  PostedBlind(u64),
   MatchedBet,
+  Raised(u64),
+  // committed the rest of their chips, they can't act again until the showdown
+  AllIn(u64),
   Folded,
   ChoseWord,
   Checked,
@@ -36,7 +242,9 @@ pub enum PlayerAction {
 #[derive(Serialize, Deserialize, Clone, JsonSchema)]
 pub struct Player {
   pub addr: HumanAddr,
-  pub secret: u64,
+  // sha256 of the secret the player reveals to seed the deck
+  pub commitment: Binary,
+  pub revealed_secret: Option<Binary>,
   pub hp: u8,
   pub hand: Vec<Card>,
   pub bet: u64,
@@ -48,29 +256,146 @@ pub struct Player {
   pub last_action: Option<PlayerAction>,
   pub nfts: Vec<SecretDreamscapeNFT>,
   pub chips: u64,
+  // left mid-hand, the seat is released when the next hand starts
+  pub left: bool,
+  // players are kept in seat order, the lowest free seat goes to whoever joins
+  pub seat: u8,
+  // joined mid-hand, holds a seat but isn't dealt in until the next hand starts
+  pub waiting: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, JsonSchema)]
 pub struct Card {
   pub letter: u8,
   pub gold: bool,
+  // worth no points, its letter is picked by the player who puts it down
+  #[serde(default)]
+  pub blank: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, JsonSchema)]
 pub struct GameBoard {
-  pub turn: u8,
+  pub turn: u32,
   pub round: GameRound,
-  pub winner_for_turn: Option<HumanAddr>,
   pub words: Vec<Word>,
   pub river: Vec<Card>,
   pub pool: u64,
-  pub rake_percentage: u64,
+
+  // block time after which a stalled round can be forced forward with ClaimTimeout
+  pub round_deadline: Option<u64>,
+
+  // the pool split by contribution level, the first one is the main pot
+  pub pots: Vec<Pot>,
+
+  // raises made in the current matching round
+  pub raises: u8,
+
+  // stacks of players who left mid-hand, paid out once the hand is settled
+  pub pending_withdrawals: Vec<Withdrawal>,
+
+  // seat of the dealer, the action starts at the next seat and the button moves every hand
+  pub dealer: u8,
+
+  // the only player who may bet, check, match, raise or fold, None when nobody has to act
+  pub action_on: Option<HumanAddr>,
+
+  // players who joined mid-hand, in the order they're dealt in when the next hand starts
+  pub join_queue: Vec<HumanAddr>,
+
+  // record of the hand in play, archived to the hand history once it's settled
+  pub hand_log: HandRecord,
+}
+
+#[derive(Serialize, Deserialize, Clone, JsonSchema, Default)]
+pub struct HandRecord {
+  pub turn: u32,
+  // only filled in once the river is shown, hands that end before stay without it
+  pub river: Vec<Card>,
+  // what everyone was dealt, only shown to the player it belongs to
+  pub hands: Vec<DealtHand>,
+  pub actions: Vec<LoggedAction>,
+  // only filled in when the hand went to a showdown
+  pub words: Vec<ScoredWord>,
+  pub payouts: Vec<Payout>,
+  pub rake: u64,
+  // None while the hand is in play
+  pub outcome: Option<HandOutcome>,
+}
+
+impl HandRecord {
+  pub fn is_settled(&self) -> bool {
+    self.outcome.is_some()
+  }
+
+  pub fn winner(&self) -> Option<&HumanAddr> {
+    match &self.outcome {
+      Some(HandOutcome::Won(addr)) => Some(addr),
+      _ => None,
+    }
+  }
+}
+
+// how a hand was settled, a hand nobody won gives everyone their bets back
+#[derive(Serialize, Deserialize, Clone, JsonSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HandOutcome {
+  Won(HumanAddr),
+  Refunded,
+}
+
+#[derive(Serialize, Deserialize, Clone, JsonSchema)]
+pub struct DealtHand {
+  pub addr: HumanAddr,
+  pub cards: Vec<Card>,
+}
+
+#[derive(Serialize, Deserialize, Clone, JsonSchema)]
+pub struct LoggedAction {
+  pub addr: HumanAddr,
+  pub round: GameRound,
+  pub action: PlayerAction,
+}
+
+#[derive(Serialize, Deserialize, Clone, JsonSchema)]
+pub struct ScoredWord {
+  pub player_addr: HumanAddr,
+  pub cards: Vec<Card>,
+  pub points: u16,
+}
+
+#[derive(Serialize, Deserialize, Clone, JsonSchema, PartialEq)]
+pub struct Payout {
+  pub addr: HumanAddr,
+  pub amount: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, JsonSchema, PartialEq)]
+pub struct Withdrawal {
+  pub addr: HumanAddr,
+  pub amount: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, JsonSchema, PartialEq)]
+pub struct Pot {
+  pub amount: u64,
+  // players that put in enough to win this pot and haven't folded
+  pub eligible: Vec<HumanAddr>,
 }
 
 #[derive(Serialize, Deserialize, Clone, JsonSchema)]
 pub struct Word {
   pub cards: Vec<Card>,
   pub player_addr: HumanAddr,
+  // id of the word in the table's dictionary when it was put down, None if it isn't in it
+  pub word_id: Option<u32>,
+}
+
+// shows a word is a leaf of the dictionary's merkle tree, the id is its position among the
+// leaves and the siblings are the hashes on the way up to the root
+#[derive(Serialize, Deserialize, Clone, JsonSchema, PartialEq)]
+pub struct WordProof {
+  pub word_id: u32,
+  pub siblings: Vec<Binary>,
 }
 
 #[derive(Serialize, Deserialize, Clone, JsonSchema, PartialEq)]
@@ -78,6 +403,9 @@ pub enum GameRound {
   // turn not started yet, initial state
   None,
 
+  // Players reveal the secrets they committed to when joining, the deck is shuffled once all are in
+  Reveal,
+
   // Community cards are not shown and every player must either bet or check
   Blind,
 
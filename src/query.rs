@@ -1,19 +1,57 @@
-use cosmwasm_std::{to_binary, Api, Extern, HumanAddr, Querier, QueryResult, StdError, Storage};
+use cosmwasm_std::{
+  to_binary, Api, Extern, HumanAddr, Querier, QueryResult, StdError, StdResult, Storage,
+};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use serde_json_wasm as serde_json;
 
-use crate::constants::{NOT_IN_GAME, WAITING_FOR_PLAYERS};
-use crate::game_state::{Card, GameRound, Player, PlayerAction, State, Word};
-use crate::utils::cards::get_score_for_word;
-use crate::utils::general::get_non_folded_players;
+use crate::constants::{MAX_HAND_HISTORY_PAGE, NOT_IN_GAME, WAITING_FOR_PLAYERS};
+use crate::game_state::{
+  Card, Currency, Dictionary, GameRound, HandRecord, Player, PlayerAction, Pot, State, TableConfig,
+  Withdrawal, Word,
+};
+use crate::utils::cards::get_score_for_played_word;
+use crate::utils::general::{get_non_folded_players, is_river_shown};
+use crate::utils::permits::{validate_permit, Permit};
+use crate::utils::storage::{
+  load_config, load_hand_history, load_hand_history_len, load_seats, load_state,
+};
+use crate::utils::viewing_keys::check_viewing_key;
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
   GetResult {},
   CanJoin {},
-  GetGameState { secret: u64 },
+  GetConfig {},
+  // everything on the table but the hands, open to spectators
+  GetPublicState {},
+  // settled hands from the `start`th archived one on, without what anyone was dealt
+  GetHandHistory {
+    start: u32,
+    limit: u32,
+  },
+  GetGameState {
+    address: HumanAddr,
+    key: String,
+  },
+  // settled hands including what the viewing key holder was dealt in each of them
+  GetPlayerHandHistory {
+    address: HumanAddr,
+    key: String,
+    start: u32,
+    limit: u32,
+  },
+  WithPermit {
+    permit: Permit,
+    query: QueryWithPermit,
+  },
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryWithPermit {
+  GetGameState {},
+  GetHandHistory { start: u32, limit: u32 },
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
@@ -29,7 +67,7 @@ pub struct GameState {
 
   pub players: Vec<PlayerStatus>,
 
-  pub turn: u8,
+  pub turn: u32,
   pub round: GameRound,
 
   pub hand: Vec<Card>,
@@ -40,6 +78,44 @@ pub struct GameState {
   pub level_design: u64,
   pub min_buy: u64,
   pub max_buy: u64,
+  pub round_deadline: Option<u64>,
+  pub pots: Vec<Pot>,
+  pub raises: u8,
+  pub pending_withdrawals: Vec<Withdrawal>,
+  pub dealer: u8,
+  pub action_on: Option<HumanAddr>,
+  pub join_queue: Vec<HumanAddr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PublicState {
+  pub pool: u64,
+
+  pub players: Vec<PlayerStatus>,
+
+  pub turn: u32,
+  pub round: GameRound,
+
+  pub river: Option<Vec<Card>>,
+
+  // only filled in once every word of the hand is down
+  pub words: Vec<WordView>,
+  pub winner: Option<HumanAddr>,
+  pub level_design: u64,
+  pub round_deadline: Option<u64>,
+  pub pots: Vec<Pot>,
+  pub dealer: u8,
+  pub action_on: Option<HumanAddr>,
+  pub join_queue: Vec<HumanAddr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct HandHistoryResponse {
+  pub hands: Vec<HandRecord>,
+  // hands archived so far, to page through the rest
+  pub total: u32,
 }
 
 #[derive(Serialize, Deserialize, Clone, JsonSchema)]
@@ -53,26 +129,44 @@ pub struct WordView {
 #[derive(Serialize, Deserialize, Clone, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct PlayerStatus {
-  hp: u8,
+  pub hp: u8,
   bet: u64,
   addr: HumanAddr,
-  folded: bool,
+  pub folded: bool,
   pub last_action: Option<PlayerAction>,
   opened_dictionary: bool,
-  chips: u64,
+  pub chips: u64,
+  pub left: bool,
+  pub seat: u8,
+  pub waiting: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct CanJoinResponse {
-  can_join: bool,
+  pub can_join: bool,
   started_time: u64,
   pub requires_password: bool,
 }
 
-fn get_stats_for_players(saved_state: &State, output_state: &mut GameState) {
+#[derive(Serialize, Deserialize, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ConfigResponse {
+  pub table: TableConfig,
+  pub currency: Currency,
+  pub dictionary: Dictionary,
+  pub admin: Option<HumanAddr>,
+  pub min_buy: u64,
+  pub max_buy: u64,
+  // the raise the table's rules come down to when they don't set one
+  pub min_raise: u64,
+  pub requires_password: bool,
+}
+
+fn get_stats_for_players(saved_state: &State) -> Vec<PlayerStatus> {
+  let mut players = vec![];
   for player in &saved_state.players {
-    output_state.players.push(PlayerStatus {
+    players.push(PlayerStatus {
       hp: player.clone().hp,
       addr: player.clone().addr,
       bet: if saved_state.game_board.round == GameRound::Flop
@@ -87,17 +181,17 @@ fn get_stats_for_players(saved_state: &State, output_state: &mut GameState) {
       last_action: player.clone().last_action,
       opened_dictionary: player.clone().opened_dictionary,
       chips: player.clone().chips,
+      left: player.left,
+      seat: player.seat,
+      waiting: player.waiting,
     })
   }
+  players
 }
 
-fn get_hand(
-  secret: &u64,
-  saved_state: &State,
-  output_state: &mut GameState,
-) -> serde::__private::Result<(), StdError> {
+fn get_hand(addr: &HumanAddr, saved_state: &State, output_state: &mut GameState) -> StdResult<()> {
   for player in &saved_state.players {
-    if &player.secret == secret {
+    if &player.addr == addr {
       output_state.hand = (*player.hand).to_owned();
       return Ok(());
     }
@@ -105,31 +199,27 @@ fn get_hand(
   Err(StdError::generic_err(NOT_IN_GAME))
 }
 
-fn get_words(
-  secret: &u64,
-  saved_state: &State,
-  output_state: &mut GameState,
-) -> serde::__private::Result<(), StdError> {
+fn get_words(addr: &HumanAddr, saved_state: &State, output_state: &mut GameState) -> StdResult<()> {
   if saved_state.game_board.round != GameRound::Choice {
     return Ok(());
   }
 
-  let mut player_with_secret: Option<Player> = None;
+  let mut requesting_player: Option<Player> = None;
   for player in &saved_state.players {
-    if &player.secret == secret {
-      player_with_secret = Some(player.clone());
+    if &player.addr == addr {
+      requesting_player = Some(player.clone());
       break;
     }
   }
 
-  if let Some(player) = player_with_secret {
+  if let Some(player) = requesting_player {
     let non_folded_players = get_non_folded_players(saved_state);
     let words_submitted_count = saved_state.game_board.words.len();
     for word in &saved_state.game_board.words {
       if words_submitted_count == non_folded_players.len() {
         output_state.words.push(WordView {
           word: Some(word.clone()),
-          points: get_score_for_word(&word.cards),
+          points: get_score_for_played_word(word, &saved_state.dictionary),
           visible: true,
         });
       } else {
@@ -141,9 +231,14 @@ fn get_words(
               vec![]
             },
             player_addr: word.player_addr.clone(),
+            word_id: if word.player_addr == player.addr {
+              word.word_id
+            } else {
+              None
+            },
           }),
           points: if word.player_addr == player.addr {
-            get_score_for_word(&word.cards)
+            get_score_for_played_word(word, &saved_state.dictionary)
           } else {
             0
           },
@@ -157,19 +252,119 @@ fn get_words(
   }
 }
 
-fn get_river(saved_state: &State, output_state: &mut GameState) {
-  match saved_state.game_board.round {
-    GameRound::None | GameRound::Blind | GameRound::Matching => {}
-    GameRound::Flop | GameRound::Matching2 | GameRound::Choice => {
-      output_state.river = Some((*saved_state.game_board.river).to_owned());
-    }
+fn get_river(saved_state: &State) -> Option<Vec<Card>> {
+  if is_river_shown(&saved_state.game_board.round) {
+    Some((*saved_state.game_board.river).to_owned())
+  } else {
+    None
+  }
+}
+
+// words of the showdown, but only once they're all down and nobody can react to them anymore
+fn get_revealed_words(saved_state: &State) -> Vec<WordView> {
+  let mut words = vec![];
+  if saved_state.game_board.round != GameRound::Choice {
+    return words;
   }
+  let non_folded_players = get_non_folded_players(saved_state);
+  if saved_state.game_board.words.len() != non_folded_players.len() {
+    return words;
+  }
+  for word in &saved_state.game_board.words {
+    words.push(WordView {
+      word: Some(word.clone()),
+      points: get_score_for_played_word(word, &saved_state.dictionary),
+      visible: true,
+    });
+  }
+  words
+}
+
+fn get_game_state<S: Storage, A: Api, Q: Querier>(
+  deps: &Extern<S, A, Q>,
+  address: &HumanAddr,
+) -> QueryResult {
+  let saved_state = load_state(&deps.storage)?;
+
+  let mut output_state = GameState {
+    words: vec![],
+    pool: saved_state.game_board.pool,
+    river: get_river(&saved_state),
+    turn: saved_state.game_board.turn,
+    winner: if let Some(winner) = saved_state.winner.clone() {
+      Some(winner)
+    } else {
+      saved_state.game_board.hand_log.winner().cloned()
+    },
+    round: saved_state.game_board.round.clone(),
+    players: get_stats_for_players(&saved_state),
+    hand: vec![],
+    level_design: saved_state.level_design,
+    min_buy: saved_state.min_buy,
+    max_buy: saved_state.max_buy,
+    round_deadline: saved_state.game_board.round_deadline,
+    pots: saved_state.game_board.pots.clone(),
+    raises: saved_state.game_board.raises,
+    pending_withdrawals: saved_state.game_board.pending_withdrawals.clone(),
+    dealer: saved_state.game_board.dealer,
+    action_on: saved_state.game_board.action_on.clone(),
+    join_queue: saved_state.game_board.join_queue.clone(),
+  };
+
+  get_hand(address, &saved_state, &mut output_state)?;
+  get_words(address, &saved_state, &mut output_state)?;
+
+  Ok(to_binary(&output_state).unwrap())
+}
+
+fn get_public_state<S: Storage, A: Api, Q: Querier>(deps: &Extern<S, A, Q>) -> QueryResult {
+  let saved_state = load_state(&deps.storage)?;
+
+  let output_state = PublicState {
+    pool: saved_state.game_board.pool,
+    players: get_stats_for_players(&saved_state),
+    turn: saved_state.game_board.turn,
+    round: saved_state.game_board.round.clone(),
+    river: get_river(&saved_state),
+    words: get_revealed_words(&saved_state),
+    winner: if let Some(winner) = saved_state.winner.clone() {
+      Some(winner)
+    } else {
+      saved_state.game_board.hand_log.winner().cloned()
+    },
+    level_design: saved_state.level_design,
+    round_deadline: saved_state.game_board.round_deadline,
+    pots: saved_state.game_board.pots.clone(),
+    dealer: saved_state.game_board.dealer,
+    action_on: saved_state.game_board.action_on.clone(),
+    join_queue: saved_state.game_board.join_queue.clone(),
+  };
+
+  Ok(to_binary(&output_state).unwrap())
+}
+
+// the dealt hands stay hidden, except the one of `viewer`
+fn get_hand_history<S: Storage, A: Api, Q: Querier>(
+  deps: &Extern<S, A, Q>,
+  viewer: Option<&HumanAddr>,
+  start: u32,
+  limit: u32,
+) -> QueryResult {
+  let mut hands = load_hand_history(&deps.storage, start, limit.min(MAX_HAND_HISTORY_PAGE))?;
+  for hand in hands.iter_mut() {
+    hand.hands.retain(|h| Some(&h.addr) == viewer);
+  }
+  let resp = HandHistoryResponse {
+    hands,
+    total: load_hand_history_len(&deps.storage)?,
+  };
+  Ok(to_binary(&resp).unwrap())
 }
 
 pub fn query<S: Storage, A: Api, Q: Querier>(deps: &Extern<S, A, Q>, msg: QueryMsg) -> QueryResult {
   match msg {
     QueryMsg::GetResult {} => {
-      let state: State = serde_json::from_slice(&deps.storage.get(b"state").unwrap()).unwrap();
+      let state = load_state(&deps.storage)?;
 
       if state.winner.is_none() {
         return Err(StdError::generic_err(WAITING_FOR_PLAYERS));
@@ -178,43 +373,53 @@ pub fn query<S: Storage, A: Api, Q: Querier>(deps: &Extern<S, A, Q>, msg: QueryM
       to_binary(&vec![state.winner.unwrap()])
     }
     QueryMsg::CanJoin {} => {
-      let state: State = serde_json::from_slice(&deps.storage.get(b"state").unwrap()).unwrap();
-      let can_join = state.players.len() < 4;
+      let config = load_config(&deps.storage)?;
+      let can_join = load_seats(&deps.storage)?.len() < config.table.max_players as usize;
       let resp = CanJoinResponse {
         can_join,
-        started_time: state.started_time,
-        requires_password: state.password.is_some(),
+        started_time: config.started_time,
+        requires_password: config.password.is_some(),
       };
       Ok(to_binary(&resp).unwrap())
     }
-    QueryMsg::GetGameState { secret } => {
-      let saved_state: State =
-        serde_json::from_slice(&deps.storage.get(b"state").unwrap()).unwrap();
-
-      let mut output_state = GameState {
-        words: vec![],
-        pool: saved_state.game_board.pool,
-        river: None,
-        turn: saved_state.game_board.turn,
-        winner: if let Some(winner) = saved_state.winner.clone() {
-          Some(winner)
-        } else {
-          saved_state.game_board.winner_for_turn.clone()
-        },
-        round: saved_state.game_board.round.clone(),
-        players: vec![],
-        hand: vec![],
-        level_design: saved_state.level_design,
-        min_buy: saved_state.min_buy,
-        max_buy: saved_state.max_buy,
+    QueryMsg::GetConfig {} => {
+      let config = load_config(&deps.storage)?;
+      let resp = ConfigResponse {
+        min_raise: config.table.smallest_raise(),
+        table: config.table,
+        currency: config.currency,
+        dictionary: config.dictionary,
+        admin: config.admin,
+        min_buy: config.min_buy,
+        max_buy: config.max_buy,
+        requires_password: config.password.is_some(),
       };
-
-      get_stats_for_players(&saved_state, &mut output_state);
-      get_hand(&secret, &saved_state, &mut output_state)?;
-      get_words(&secret, &saved_state, &mut output_state)?;
-      get_river(&saved_state, &mut output_state);
-
-      Ok(to_binary(&output_state).unwrap())
+      Ok(to_binary(&resp).unwrap())
+    }
+    QueryMsg::GetPublicState {} => get_public_state(deps),
+    QueryMsg::GetHandHistory { start, limit } => get_hand_history(deps, None, start, limit),
+    QueryMsg::GetGameState { address, key } => {
+      check_viewing_key(&deps.storage, &address, &key)?;
+      get_game_state(deps, &address)
+    }
+    QueryMsg::GetPlayerHandHistory {
+      address,
+      key,
+      start,
+      limit,
+    } => {
+      check_viewing_key(&deps.storage, &address, &key)?;
+      get_hand_history(deps, Some(&address), start, limit)
+    }
+    QueryMsg::WithPermit { permit, query } => {
+      let config = load_config(&deps.storage)?;
+      let address = validate_permit(deps, &permit, &config.contract_addr)?;
+      match query {
+        QueryWithPermit::GetGameState {} => get_game_state(deps, &address),
+        QueryWithPermit::GetHandHistory { start, limit } => {
+          get_hand_history(deps, Some(&address), start, limit)
+        }
+      }
     }
   }
 }
@@ -1,2 +1,9 @@
 pub mod cards;
+pub mod chips;
+pub mod dictionary;
+pub mod events;
 pub mod general;
+pub mod migration;
+pub mod permits;
+pub mod storage;
+pub mod viewing_keys;
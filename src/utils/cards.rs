@@ -1,105 +1,125 @@
-use cosmwasm_std::Env;
-use lazy_static::lazy_static;
+use cosmwasm_std::{Env, StdError, StdResult};
 use rand::{seq::SliceRandom, SeedableRng};
 use rand_chacha::ChaChaRng;
 use sha2::{Digest, Sha256};
 
-use crate::game_state::{Card, State};
+use crate::constants::OUT_OF_CARDS;
+use crate::game_state::{Card, Dictionary, State, Word};
+use crate::utils::dictionary::is_word;
 
-lazy_static! {
-  static ref ALLOWED_WORDS: Vec<&'static str> = {
-    let word_txt: &str = include_str!("../words.txt");
-    word_txt.split('\n').collect()
-  };
+pub fn get_score_for_word(cards: &[Card], dictionary: &Dictionary) -> u16 {
+  let letters: Vec<u8> = cards.iter().map(|c| c.letter).collect();
+  if !is_word(&dictionary.word_list, &letters) {
+    return 0;
+  }
+  get_points_for_cards(cards, dictionary)
+}
+
+// a word at the table was checked against the dictionary when it was put down
+pub fn get_score_for_played_word(word: &Word, dictionary: &Dictionary) -> u16 {
+  if word.word_id.is_none() {
+    return 0;
+  }
+  get_points_for_cards(&word.cards, dictionary)
 }
 
-pub fn get_score_for_word(cards: &[Card]) -> u16 {
-  let card_to_point: Vec<u16> = vec![
-    1, 3, 3, 2, 1, 4, 2, 4, 1, 8, 5, 1, 3, 1, 1, 3, 10, 1, 1, 1, 1, 4, 4, 8, 4, 8,
-  ];
+// words in the dictionary beat every word that isn't, whatever they score
+pub fn get_rank_for_played_word(word: &Word, dictionary: &Dictionary) -> (bool, u16) {
+  (
+    word.word_id.is_some(),
+    get_score_for_played_word(word, dictionary),
+  )
+}
+
+fn get_points_for_cards(cards: &[Card], dictionary: &Dictionary) -> u16 {
   let mut score: u16 = 0;
   let mut golds = 0;
-  let mut word = String::from("");
   for card in cards {
+    // a blank is worth nothing, gold or not
+    if card.blank {
+      continue;
+    }
     if card.gold {
       golds += 1;
     }
-    score += card_to_point.get(card.letter as usize).unwrap();
-    word.push((card.letter + b'A') as char);
+    score += dictionary.points.get(card.letter as usize).unwrap_or(&0);
   }
   for _ in 0..golds {
     score *= 2;
   }
-  if !ALLOWED_WORDS.contains(&&*word) {
-    return 0;
-  }
   score
 }
 
-pub fn find_word_id(word: &str) -> Option<usize> {
-  ALLOWED_WORDS.iter().position(|w| w == &word)
-}
+pub fn get_n_cards(state: &mut State, count: u8, env: &Env) -> StdResult<Vec<Card>> {
+  if state.deck.len() < count as usize {
+    reshuffle_discard_pile(state, env);
+  }
+  if state.deck.len() < count as usize {
+    return Err(StdError::generic_err(OUT_OF_CARDS));
+  }
 
-pub fn get_n_cards(state: &mut State, count: u8) -> Vec<Card> {
   let mut cards = vec![];
   for _ in 0..count {
     cards.push(state.deck.swap_remove(0))
   }
-  cards
+  Ok(cards)
+}
+
+pub fn discard(state: &mut State, cards: Vec<Card>) {
+  state.discard_pile.extend(cards);
 }
 
-pub fn generate_deck(mut rng: ChaChaRng) -> Vec<Card> {
-  let count_per_card: Vec<(u8, u8)> = vec![
-    (4, 12),
-    (0, 9),
-    (8, 9),
-    (14, 8),
-    (13, 6),
-    (17, 6),
-    (19, 6),
-    (11, 4),
-    (18, 4),
-    (20, 4),
-    (3, 4),
-    (6, 3),
-    (1, 2),
-    (2, 2),
-    (12, 2),
-    (15, 2),
-    (5, 2),
-    (7, 2),
-    (21, 2),
-    (22, 2),
-    (24, 2),
-    (10, 1),
-    (9, 1),
-    (23, 1),
-    (16, 1),
-    (25, 1),
-  ];
+fn reshuffle_discard_pile(state: &mut State, env: &Env) {
+  let mut discarded: Vec<Card> = state.discard_pile.drain(..).collect();
+  discarded.shuffle(&mut get_reshuffle_rng(state, env));
+  state.deck.append(&mut discarded);
+}
 
+pub fn generate_deck(mut rng: ChaChaRng, gold_cards: u8, dictionary: &Dictionary) -> Vec<Card> {
   let mut deck: Vec<Card> = vec![];
-  for (_, (letter, count)) in count_per_card.iter().enumerate() {
+  for (letter, count) in dictionary.distribution.iter() {
     for _ in 0..*count {
       deck.push(Card {
         letter: *letter,
         gold: false,
+        blank: false,
       })
     }
   }
+  for _ in 0..dictionary.blanks {
+    deck.push(Card {
+      letter: 0,
+      gold: false,
+      blank: true,
+    })
+  }
   deck.shuffle(&mut rng);
-  for i in 0..5 {
-    // we set the first 5 cards to be golden
-    deck[i].gold = true;
+  // we set the first few cards to be golden
+  for card in deck.iter_mut().take(gold_cards as usize) {
+    card.gold = true;
   }
   deck.shuffle(&mut rng); // and shuffle the deck again to move the gold cards around
   deck
 }
 
-pub fn get_rng(state: &State, env: &Env) -> ChaChaRng {
-  let mut combined_secret: Vec<u8> = env.block.time.to_be_bytes().to_vec();
+pub fn get_rng(state: &State) -> ChaChaRng {
+  rng_from_secrets(state, state.started_time.to_be_bytes().to_vec())
+}
+
+fn get_reshuffle_rng(state: &State, env: &Env) -> ChaChaRng {
+  // the block and hand make every reshuffle differ from the first shuffle, the secrets keep
+  // it out of any single player's hands
+  let mut entropy: Vec<u8> = env.block.height.to_be_bytes().to_vec();
+  entropy.extend(&env.block.time.to_be_bytes());
+  entropy.extend(&state.game_board.turn.to_be_bytes());
+  rng_from_secrets(state, entropy)
+}
+
+fn rng_from_secrets(state: &State, mut combined_secret: Vec<u8>) -> ChaChaRng {
   for player in state.players.iter() {
-    combined_secret.extend(&player.secret.to_be_bytes());
+    if let Some(secret) = &player.revealed_secret {
+      combined_secret.extend(secret.as_slice());
+    }
   }
   let random_seed: [u8; 32] = Sha256::digest(&combined_secret).into();
   ChaChaRng::from_seed(random_seed)
@@ -0,0 +1,57 @@
+use cosmwasm_std::{
+  to_binary, BankMsg, Coin, CosmosMsg, Env, HumanAddr, StdResult, Uint128, WasmMsg,
+};
+use secret_toolkit::snip20::{send_msg, transfer_msg};
+use secret_toolkit::utils::space_pad;
+
+use crate::contract::SecretDreamscapeJackpot;
+use crate::game_state::{Currency, State};
+
+// same padding the rest of the contract's messages use
+const BLOCK_SIZE: usize = 256;
+
+// pays chips out in the table's currency
+pub fn send_chips(state: &State, env: &Env, to: &HumanAddr, amount: u64) -> StdResult<CosmosMsg> {
+  match &state.currency {
+    Currency::Native { denom } => Ok(CosmosMsg::Bank(BankMsg::Send {
+      from_address: env.contract.address.clone(),
+      to_address: to.clone(),
+      amount: vec![Coin::new(amount as u128, denom)],
+    })),
+    Currency::Snip20 { addr, hash } => transfer_msg(
+      to.clone(),
+      Uint128(amount as u128),
+      None,
+      None,
+      BLOCK_SIZE,
+      hash.clone(),
+      addr.clone(),
+    ),
+  }
+}
+
+// tokens reach the jackpot through a snip20 Send carrying the Fund message
+pub fn fund_jackpot(state: &State, amount: u64) -> StdResult<CosmosMsg> {
+  let mut msg = to_binary(&SecretDreamscapeJackpot::Fund {})?;
+  match &state.currency {
+    Currency::Native { denom } => {
+      space_pad(&mut msg.0, BLOCK_SIZE);
+      Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: state.jackpot_addr.clone(),
+        callback_code_hash: state.jackpot_hash.clone(),
+        msg,
+        send: vec![Coin::new(amount as u128, denom)],
+      }))
+    }
+    Currency::Snip20 { addr, hash } => send_msg(
+      state.jackpot_addr.clone(),
+      Uint128(amount as u128),
+      Some(msg),
+      None,
+      None,
+      BLOCK_SIZE,
+      hash.clone(),
+      addr.clone(),
+    ),
+  }
+}
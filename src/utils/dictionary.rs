@@ -0,0 +1,125 @@
+use cosmwasm_std::Binary;
+use sha2::{Digest, Sha256};
+
+use crate::game_state::{WordList, WordProof};
+
+// the word lists sorted and packed by build.rs, every word takes 5 bytes with 5 bits per
+// letter, along with their alphabets and the (rank, id) of the words that aren't on the line
+// of their file their sorted rank points at. Without the `word-lists` feature the lists are
+// empty and only the alphabets are there
+include!(concat!(env!("OUT_DIR"), "/word_lists.rs"));
+
+const MAX_LETTERS: usize = 8;
+const BYTES_PER_WORD: usize = 5;
+
+struct PackedList {
+  words: &'static [u8],
+  alphabet: &'static [&'static str],
+  moved_ids: &'static [(u32, u32)],
+}
+
+fn packed_list(list: &WordList) -> PackedList {
+  match list {
+    WordList::English => PackedList {
+      words: ENGLISH_WORDS,
+      alphabet: &ENGLISH_ALPHABET,
+      moved_ids: &ENGLISH_MOVED_IDS,
+    },
+  }
+}
+
+// symbol of every letter a card of this list can have, the letter of a card is its index
+pub fn alphabet(list: &WordList) -> &'static [&'static str] {
+  packed_list(list).alphabet
+}
+
+// same packing as build.rs, None for anything that can't be in the list
+fn pack(letters: &[u8], alphabet_len: usize) -> Option<u64> {
+  if letters.is_empty() || letters.len() > MAX_LETTERS {
+    return None;
+  }
+  let mut key: u64 = 0;
+  for letter in letters {
+    if *letter as usize >= alphabet_len {
+      return None;
+    }
+    key = key << 5 | (letter + 1) as u64;
+  }
+  Some(key << (5 * (MAX_LETTERS - letters.len())))
+}
+
+fn key_at(words: &[u8], rank: usize) -> u64 {
+  let mut key: u64 = 0;
+  for byte in &words[rank * BYTES_PER_WORD..(rank + 1) * BYTES_PER_WORD] {
+    key = key << 8 | *byte as u64;
+  }
+  key
+}
+
+// position of the word in the sorted list
+fn find_rank(list: &PackedList, letters: &[u8]) -> Option<usize> {
+  let key = pack(letters, list.alphabet.len())?;
+  let mut low = 0;
+  let mut high = list.words.len() / BYTES_PER_WORD;
+  while low < high {
+    let middle = (low + high) / 2;
+    let middle_key = key_at(list.words, middle);
+    if middle_key == key {
+      return Some(middle);
+    } else if middle_key < key {
+      low = middle + 1;
+    } else {
+      high = middle;
+    }
+  }
+  None
+}
+
+pub fn is_word(list: &WordList, letters: &[u8]) -> bool {
+  find_rank(&packed_list(list), letters).is_some()
+}
+
+// line of the word in its file, which stays its id whatever order the file is in
+pub fn find_word_id(list: &WordList, letters: &[u8]) -> Option<usize> {
+  let list = packed_list(list);
+  let rank = find_rank(&list, letters)?;
+  match list
+    .moved_ids
+    .binary_search_by_key(&(rank as u32), |&(moved_rank, _)| moved_rank)
+  {
+    Ok(index) => Some(list.moved_ids[index].1 as usize),
+    Err(_) => Some(rank),
+  }
+}
+
+// leaves and nodes are hashed with a different first byte so a node can't pass as a word
+pub fn word_leaf(word_id: u32, letters: &[u8]) -> [u8; 32] {
+  let mut preimage = vec![0];
+  preimage.extend_from_slice(&word_id.to_be_bytes());
+  preimage.extend_from_slice(letters);
+  Sha256::digest(&preimage).into()
+}
+
+pub fn merkle_node(left: &[u8], right: &[u8]) -> [u8; 32] {
+  let mut preimage = vec![1];
+  preimage.extend_from_slice(left);
+  preimage.extend_from_slice(right);
+  Sha256::digest(&preimage).into()
+}
+
+// the bits of the id, from the lowest, tell whether every sibling is on the left or the right
+pub fn verify_word_proof(root: &Binary, letters: &[u8], proof: &WordProof) -> bool {
+  if proof.siblings.len() > 32 {
+    return false;
+  }
+  let mut node = word_leaf(proof.word_id, letters);
+  for i in 0..proof.siblings.len() {
+    let sibling = proof.siblings[i].as_slice();
+    node = if proof.word_id >> i & 1 == 0 {
+      merkle_node(&node, sibling)
+    } else {
+      merkle_node(sibling, &node)
+    };
+  }
+  root.as_slice() == node
+}
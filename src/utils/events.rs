@@ -0,0 +1,107 @@
+use cosmwasm_std::{log, Env, HumanAddr, LogAttribute};
+
+use crate::contract::HandleMsg;
+use crate::game_state::{GameRound, HandOutcome, State};
+
+// what a handle call is about to change, compared against the state it leaves behind
+pub struct Snapshot {
+  round: GameRound,
+  turn: u32,
+  hand_settled: bool,
+  game_over: bool,
+}
+
+impl Snapshot {
+  pub fn of(state: &State) -> Self {
+    Snapshot {
+      round: state.game_board.round.clone(),
+      turn: state.game_board.turn,
+      hand_settled: state.game_board.hand_log.is_settled(),
+      game_over: state.winner.is_some(),
+    }
+  }
+}
+
+// name of the action, who it's for and the amount it moves, if any
+pub fn describe_action(env: &Env, msg: &HandleMsg) -> (&'static str, HumanAddr, Option<u64>) {
+  let sender = env.message.sender.clone();
+  match msg {
+    HandleMsg::Join { .. } => ("join", sender, None),
+    HandleMsg::BuyChips {} => {
+      let amount = env
+        .message
+        .sent_funds
+        .iter()
+        .map(|c| c.amount.u128())
+        .sum::<u128>();
+      ("buy_chips", sender, Some(amount as u64))
+    }
+    // snip20 deposits are sent by the token contract on behalf of the player
+    HandleMsg::Receive { from, amount, .. } => {
+      ("buy_chips", from.clone(), Some(amount.u128() as u64))
+    }
+    HandleMsg::Bet { amount } => ("bet", sender, Some(*amount)),
+    HandleMsg::Match { amount } => ("match", sender, Some(*amount)),
+    HandleMsg::Raise { amount } => ("raise", sender, Some(*amount)),
+    HandleMsg::Fold {} => ("fold", sender, None),
+    HandleMsg::Check {} => ("check", sender, None),
+    HandleMsg::CashOut { amount } => ("cash_out", sender, Some(*amount)),
+    HandleMsg::Leave {} => ("leave", sender, None),
+    HandleMsg::PutDownCard { .. } => ("put_down_card", sender, None),
+    HandleMsg::Reveal { .. } => ("reveal", sender, None),
+    HandleMsg::RequestNextTurn {} => ("request_next_turn", sender, None),
+    HandleMsg::ClaimTimeout {} => ("claim_timeout", sender, None),
+    HandleMsg::CreateViewingKey { .. } => ("create_viewing_key", sender, None),
+    HandleMsg::SetViewingKey { .. } => ("set_viewing_key", sender, None),
+    HandleMsg::RevokePermit { .. } => ("revoke_permit", sender, None),
+    HandleMsg::SetDictionaryRoot { .. } => ("set_dictionary_root", sender, None),
+  }
+}
+
+pub fn action_logs(
+  action: &str,
+  player: &HumanAddr,
+  amount: Option<u64>,
+  before: &Snapshot,
+  after: &State,
+) -> Vec<LogAttribute> {
+  let mut logs = vec![log("action", action), log("player", player)];
+  if let Some(amount) = amount {
+    logs.push(log("amount", amount));
+  }
+  if after.game_board.turn != before.turn {
+    logs.push(log("turn", after.game_board.turn));
+  }
+  if after.game_board.round != before.round || after.game_board.turn != before.turn {
+    logs.push(log("round", round_name(&after.game_board.round)));
+  }
+
+  let hand_settled = after.game_board.hand_log.is_settled()
+    && (!before.hand_settled || after.game_board.turn != before.turn);
+  if hand_settled {
+    match &after.game_board.hand_log.outcome {
+      Some(HandOutcome::Won(winner)) => logs.push(log("winner", winner)),
+      _ => logs.push(log("refunded", true)),
+    }
+    for payout in after.game_board.hand_log.payouts.iter() {
+      logs.push(log("payout", format!("{}:{}", payout.addr, payout.amount)));
+    }
+    logs.push(log("rake", after.game_board.hand_log.rake));
+  }
+  if let (false, Some(winner)) = (before.game_over, &after.winner) {
+    logs.push(log("game_winner", winner));
+  }
+  logs
+}
+
+fn round_name(round: &GameRound) -> &'static str {
+  match round {
+    GameRound::None => "none",
+    GameRound::Reveal => "reveal",
+    GameRound::Blind => "blind",
+    GameRound::Matching => "matching",
+    GameRound::Flop => "flop",
+    GameRound::Matching2 => "matching2",
+    GameRound::Choice => "choice",
+  }
+}
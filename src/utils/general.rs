@@ -1,11 +1,24 @@
-use crate::game_state::{Player, State};
+use crate::game_state::{GameRound, Player, State};
 
 pub fn get_non_folded_players(state: &State) -> Vec<Player> {
   let mut players = vec![];
   for i in 0..state.players.len() {
-    if !state.players[i].folded {
+    if is_in_hand(&state.players[i]) {
       players.push(state.players[i].clone());
     }
   }
   players
 }
+
+// the river is shown from the flop on
+pub fn is_river_shown(round: &GameRound) -> bool {
+  match round {
+    GameRound::None | GameRound::Reveal | GameRound::Blind | GameRound::Matching => false,
+    GameRound::Flop | GameRound::Matching2 | GameRound::Choice => true,
+  }
+}
+
+// dealt into the current hand and still playing it
+pub fn is_in_hand(player: &Player) -> bool {
+  !player.folded && !player.waiting
+}
@@ -0,0 +1,431 @@
+use cosmwasm_std::{Binary, Env, HumanAddr, StdError, StdResult, Storage};
+use serde::{Deserialize, Serialize};
+use serde_json_wasm as serde_json;
+use sha2::{Digest, Sha256};
+
+use crate::constants::{SCHEMA_VERSION, UNKNOWN_SCHEMA_VERSION};
+use crate::contract::SecretDreamscapeNFT;
+use crate::game_state::{
+  Card, Currency, DamageRule, Dictionary, GameBoard, GameRound, HandOutcome, HandRecord, Player,
+  PlayerAction, State, TableConfig, Word, WordList,
+};
+use crate::utils::dictionary::find_word_id;
+use crate::utils::general::is_river_shown;
+use crate::utils::storage::{
+  load, load_player_record, load_schema_version, load_seats, save_state, BOARD_KEY, CONFIG_KEY,
+  DECK_KEY,
+};
+
+// key of the json blob tables stored everything under before the schema was versioned
+static LEGACY_STATE_KEY: &[u8] = b"state";
+
+#[derive(Serialize, Deserialize)]
+struct LegacyState {
+  players: Vec<LegacyPlayer>,
+  can_join: bool,
+
+  game_board: LegacyGameBoard,
+  winner: Option<HumanAddr>,
+  deck: Vec<Card>,
+  started_time: u64,
+  level_design: u64,
+  password: Option<String>,
+  stamp_hash: String,
+  stamp_addr: HumanAddr,
+
+  min_buy: u64,
+  max_buy: u64,
+  jackpot_addr: HumanAddr,
+  jackpot_hash: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LegacyPlayer {
+  addr: HumanAddr,
+  secret: u64,
+  hp: u8,
+  hand: Vec<Card>,
+  bet: u64,
+  bet2: u64,
+  folded: bool,
+  checked: bool,
+  checked2: bool,
+  opened_dictionary: bool,
+  last_action: Option<PlayerAction>,
+  nfts: Vec<SecretDreamscapeNFT>,
+  chips: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LegacyWord {
+  cards: Vec<Card>,
+  player_addr: HumanAddr,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LegacyGameBoard {
+  turn: u8,
+  round: GameRound,
+  winner_for_turn: Option<HumanAddr>,
+  words: Vec<LegacyWord>,
+  river: Vec<Card>,
+  pool: u64,
+  rake_percentage: u64,
+}
+
+// records of the first versioned layout, from before tables had their own rules, currency and
+// dictionary, and before hands were archived. Players, seats and the deck keep their keys
+#[derive(Serialize, Deserialize)]
+struct V1Config {
+  started_time: u64,
+  level_design: u64,
+  password: Option<String>,
+  stamp_hash: String,
+  stamp_addr: HumanAddr,
+  min_buy: u64,
+  max_buy: u64,
+  jackpot_addr: HumanAddr,
+  jackpot_hash: String,
+  contract_addr: HumanAddr,
+  damage: DamageRule,
+  turn_timeout: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct V1Board {
+  can_join: bool,
+  game_board: V1GameBoard,
+  winner: Option<HumanAddr>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct V1GameBoard {
+  turn: u8,
+  round: GameRound,
+  winner_for_turn: Option<HumanAddr>,
+  words: Vec<V1Word>,
+  river: Vec<V1Card>,
+  pool: u64,
+  rake_percentage: u64,
+  round_deadline: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct V1Word {
+  cards: Vec<V1Card>,
+  player_addr: HumanAddr,
+}
+
+// cards had no blanks yet
+#[derive(Serialize, Deserialize)]
+struct V1Card {
+  letter: u8,
+  gold: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct V1Deck {
+  deck: Vec<V1Card>,
+  discard_pile: Vec<V1Card>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct V1Player {
+  addr: HumanAddr,
+  commitment: Binary,
+  revealed_secret: Option<Binary>,
+  hp: u8,
+  hand: Vec<V1Card>,
+  bet: u64,
+  bet2: u64,
+  folded: bool,
+  checked: bool,
+  checked2: bool,
+  opened_dictionary: bool,
+  last_action: Option<V1PlayerAction>,
+  nfts: Vec<SecretDreamscapeNFT>,
+  chips: u64,
+}
+
+// actions were added in between these since, so they're stored under other indexes now
+#[derive(Serialize, Deserialize)]
+enum V1PlayerAction {
+  SentBet(u64),
+  MatchedBet,
+  Folded,
+  ChoseWord,
+  Checked,
+}
+
+pub fn migrate_if_needed<S: Storage>(storage: &mut S, env: &Env) -> StdResult<()> {
+  match load_schema_version(storage)? {
+    Some(SCHEMA_VERSION) => Ok(()),
+    Some(1) => migrate_v1_state(storage),
+    Some(_) => Err(StdError::generic_err(UNKNOWN_SCHEMA_VERSION)),
+    None => migrate_legacy_state(storage, env),
+  }
+}
+
+fn migrate_legacy_state<S: Storage>(storage: &mut S, env: &Env) -> StdResult<()> {
+  let legacy: LegacyState = match storage.get(LEGACY_STATE_KEY) {
+    Some(bytes) => serde_json::from_slice(&bytes)
+      .map_err(|e| StdError::parse_err("LegacyState", e.to_string()))?,
+    None => return Err(StdError::not_found("State")),
+  };
+
+  save_state(storage, &upgrade_legacy_state(legacy, env))?;
+  storage.remove(LEGACY_STATE_KEY);
+  Ok(())
+}
+
+fn upgrade_legacy_state(legacy: LegacyState, env: &Env) -> State {
+  let mut players = vec![];
+  for player in legacy.players {
+    // the deck of a legacy table was already shuffled from these secrets, so they count as revealed
+    let secret = player.secret.to_be_bytes().to_vec();
+    players.push(Player {
+      addr: player.addr,
+      commitment: Binary(Sha256::digest(&secret).to_vec()),
+      revealed_secret: Some(Binary(secret)),
+      hp: player.hp,
+      hand: player.hand,
+      bet: player.bet,
+      bet2: player.bet2,
+      folded: player.folded,
+      checked: player.checked,
+      checked2: player.checked2,
+      opened_dictionary: player.opened_dictionary,
+      last_action: player.last_action,
+      nfts: player.nfts,
+      chips: player.chips,
+      left: false,
+      seat: players.len() as u8,
+      waiting: false,
+    });
+  }
+
+  let words = legacy
+    .game_board
+    .words
+    .into_iter()
+    .map(|word| upgrade_word(word.cards, word.player_addr))
+    .collect();
+
+  let dealer = (players.len() as u8).saturating_sub(1);
+  let table = TableConfig {
+    rake_percentage: legacy.game_board.rake_percentage,
+    ..TableConfig::default()
+  };
+  let round_deadline = match legacy.game_board.round {
+    GameRound::None | GameRound::Reveal => None,
+    _ => Some(env.block.time + table.turn_timeout),
+  };
+
+  let hand_log = hand_in_play(
+    legacy.game_board.turn,
+    &legacy.game_board.round,
+    &legacy.game_board.river,
+    legacy.game_board.winner_for_turn,
+  );
+
+  State {
+    players,
+    can_join: legacy.can_join,
+    game_board: GameBoard {
+      turn: legacy.game_board.turn as u32,
+      round: legacy.game_board.round,
+      words,
+      river: legacy.game_board.river,
+      pool: legacy.game_board.pool,
+      round_deadline,
+      pots: vec![],
+      raises: 0,
+      pending_withdrawals: vec![],
+      // the pointer is set again as soon as the round moves on
+      dealer,
+      action_on: None,
+      join_queue: vec![],
+      hand_log,
+    },
+    winner: legacy.winner,
+    deck: legacy.deck,
+    discard_pile: vec![],
+    started_time: legacy.started_time,
+    level_design: legacy.level_design,
+    password: legacy.password,
+    stamp_hash: legacy.stamp_hash,
+    stamp_addr: legacy.stamp_addr,
+    min_buy: legacy.min_buy,
+    max_buy: legacy.max_buy,
+    jackpot_addr: legacy.jackpot_addr,
+    jackpot_hash: legacy.jackpot_hash,
+    contract_addr: env.contract.address.clone(),
+    table,
+    currency: Currency::default(),
+    dictionary: dictionary_without_blanks(),
+    admin: None,
+  }
+}
+
+fn migrate_v1_state<S: Storage>(storage: &mut S) -> StdResult<()> {
+  let config: V1Config = load(storage, CONFIG_KEY)?;
+  let board: V1Board = load(storage, BOARD_KEY)?;
+  let deck: V1Deck = load(storage, DECK_KEY)?;
+  let mut players = vec![];
+  for addr in load_seats(storage)? {
+    match load_player_record(storage, &addr)? {
+      Some(player) => players.push(player),
+      None => return Err(StdError::not_found("Player")),
+    }
+  }
+
+  // every record is written again in the current layout, under the same keys
+  save_state(storage, &upgrade_v1_state(config, board, deck, players))
+}
+
+fn upgrade_v1_state(
+  config: V1Config,
+  board: V1Board,
+  deck: V1Deck,
+  v1_players: Vec<V1Player>,
+) -> State {
+  let mut players = vec![];
+  for player in v1_players {
+    players.push(Player {
+      addr: player.addr,
+      commitment: player.commitment,
+      revealed_secret: player.revealed_secret,
+      hp: player.hp,
+      hand: upgrade_cards(player.hand),
+      bet: player.bet,
+      bet2: player.bet2,
+      folded: player.folded,
+      checked: player.checked,
+      checked2: player.checked2,
+      opened_dictionary: player.opened_dictionary,
+      last_action: player.last_action.map(upgrade_action),
+      nfts: player.nfts,
+      chips: player.chips,
+      left: false,
+      seat: players.len() as u8,
+      waiting: false,
+    });
+  }
+
+  let game_board = board.game_board;
+  let words = game_board
+    .words
+    .into_iter()
+    .map(|word| upgrade_word(upgrade_cards(word.cards), word.player_addr))
+    .collect();
+  let river = upgrade_cards(game_board.river);
+  let hand_log = hand_in_play(
+    game_board.turn,
+    &game_board.round,
+    &river,
+    game_board.winner_for_turn,
+  );
+  let dealer = (players.len() as u8).saturating_sub(1);
+
+  State {
+    players,
+    can_join: board.can_join,
+    game_board: GameBoard {
+      turn: game_board.turn as u32,
+      round: game_board.round,
+      words,
+      river,
+      pool: game_board.pool,
+      round_deadline: game_board.round_deadline,
+      pots: vec![],
+      raises: 0,
+      pending_withdrawals: vec![],
+      // the pointer is set again as soon as the round moves on
+      dealer,
+      action_on: None,
+      join_queue: vec![],
+      hand_log,
+    },
+    winner: board.winner,
+    deck: upgrade_cards(deck.deck),
+    discard_pile: upgrade_cards(deck.discard_pile),
+    started_time: config.started_time,
+    level_design: config.level_design,
+    password: config.password,
+    stamp_hash: config.stamp_hash,
+    stamp_addr: config.stamp_addr,
+    min_buy: config.min_buy,
+    max_buy: config.max_buy,
+    jackpot_addr: config.jackpot_addr,
+    jackpot_hash: config.jackpot_hash,
+    contract_addr: config.contract_addr,
+    table: TableConfig {
+      damage: config.damage,
+      turn_timeout: config.turn_timeout,
+      rake_percentage: game_board.rake_percentage,
+      ..TableConfig::default()
+    },
+    currency: Currency::default(),
+    dictionary: dictionary_without_blanks(),
+    admin: None,
+  }
+}
+
+fn upgrade_cards(cards: Vec<V1Card>) -> Vec<Card> {
+  cards
+    .into_iter()
+    .map(|card| Card {
+      letter: card.letter,
+      gold: card.gold,
+      blank: false,
+    })
+    .collect()
+}
+
+fn upgrade_action(action: V1PlayerAction) -> PlayerAction {
+  match action {
+    V1PlayerAction::SentBet(amount) => PlayerAction::SentBet(amount),
+    V1PlayerAction::MatchedBet => PlayerAction::MatchedBet,
+    V1PlayerAction::Folded => PlayerAction::Folded,
+    V1PlayerAction::ChoseWord => PlayerAction::ChoseWord,
+    V1PlayerAction::Checked => PlayerAction::Checked,
+  }
+}
+
+// older words were only looked up in the english list once they were scored
+fn upgrade_word(cards: Vec<Card>, player_addr: HumanAddr) -> Word {
+  let letters: Vec<u8> = cards.iter().map(|c| c.letter).collect();
+  Word {
+    word_id: find_word_id(&WordList::English, &letters).map(|id| id as u32),
+    cards,
+    player_addr,
+  }
+}
+
+// the hand in play is archived under its own turn once it's settled, older layouts only kept
+// who won it
+fn hand_in_play(
+  turn: u8,
+  round: &GameRound,
+  river: &[Card],
+  winner_for_turn: Option<HumanAddr>,
+) -> HandRecord {
+  HandRecord {
+    turn: turn as u32,
+    river: if is_river_shown(round) {
+      river.to_vec()
+    } else {
+      vec![]
+    },
+    outcome: winner_for_turn.map(HandOutcome::Won),
+    ..HandRecord::default()
+  }
+}
+
+// older tables dealt their decks without blanks and keep doing so
+fn dictionary_without_blanks() -> Dictionary {
+  Dictionary {
+    blanks: 0,
+    ..Dictionary::default()
+  }
+}
@@ -0,0 +1,164 @@
+use bech32::ToBase32;
+use cosmwasm_std::{
+  Api, Binary, Extern, HumanAddr, Querier, ReadonlyStorage, StdError, StdResult, Storage,
+};
+use cosmwasm_storage::{PrefixedStorage, ReadonlyPrefixedStorage};
+use ripemd160::Ripemd160;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json_wasm as serde_json;
+use sha2::{Digest, Sha256};
+
+use crate::constants::{INVALID_PERMIT, PERMIT_REVOKED};
+
+static REVOKED_PERMITS: &[u8] = b"revoked_permits";
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Permit {
+  pub params: PermitParams,
+  pub signature: PermitSignature,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PermitParams {
+  pub allowed_tokens: Vec<HumanAddr>,
+  pub permit_name: String,
+  pub chain_id: String,
+  pub permissions: Vec<Permission>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+  // lets the bearer read everything the signer could see with their viewing key
+  Owner,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PermitSignature {
+  pub pub_key: PubKey,
+  pub signature: Binary,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PubKey {
+  pub r#type: String,
+  pub value: Binary,
+}
+
+// The amino sign doc wallets produce for a permit. Fields are declared in alphabetical order
+// so the serialized json matches the canonical form that was signed.
+#[derive(Serialize)]
+struct SignedPermit<'a> {
+  account_number: &'static str,
+  chain_id: &'a str,
+  fee: Fee,
+  memo: &'static str,
+  msgs: [PermitMsg<'a>; 1],
+  sequence: &'static str,
+}
+
+#[derive(Serialize)]
+struct Fee {
+  amount: [FeeCoin; 1],
+  gas: &'static str,
+}
+
+#[derive(Serialize)]
+struct FeeCoin {
+  amount: &'static str,
+  denom: &'static str,
+}
+
+#[derive(Serialize)]
+struct PermitMsg<'a> {
+  r#type: &'static str,
+  value: PermitContent<'a>,
+}
+
+#[derive(Serialize)]
+struct PermitContent<'a> {
+  allowed_tokens: &'a [HumanAddr],
+  permissions: &'a [Permission],
+  permit_name: &'a str,
+}
+
+pub fn pubkey_to_address(pubkey: &[u8]) -> HumanAddr {
+  let hash = Ripemd160::digest(&Sha256::digest(pubkey));
+  HumanAddr(bech32::encode("secret", hash.to_base32()).unwrap())
+}
+
+/// Returns the address of the signer if the permit is valid for this contract
+pub fn validate_permit<S: Storage, A: Api, Q: Querier>(
+  deps: &Extern<S, A, Q>,
+  permit: &Permit,
+  contract_addr: &HumanAddr,
+) -> StdResult<HumanAddr> {
+  if !permit.params.allowed_tokens.contains(contract_addr)
+    || !permit.params.permissions.contains(&Permission::Owner)
+  {
+    return Err(StdError::generic_err(INVALID_PERMIT));
+  }
+
+  let signed = SignedPermit {
+    account_number: "0",
+    chain_id: &permit.params.chain_id,
+    fee: Fee {
+      amount: [FeeCoin {
+        amount: "0",
+        denom: "uscrt",
+      }],
+      gas: "1",
+    },
+    memo: "",
+    msgs: [PermitMsg {
+      r#type: "query_permit",
+      value: PermitContent {
+        allowed_tokens: &permit.params.allowed_tokens,
+        permissions: &permit.params.permissions,
+        permit_name: &permit.params.permit_name,
+      },
+    }],
+    sequence: "0",
+  };
+  let signed_bytes =
+    serde_json::to_vec(&signed).map_err(|_| StdError::generic_err(INVALID_PERMIT))?;
+  let pubkey = permit.signature.pub_key.value.as_slice();
+  let verified = deps
+    .api
+    .secp256k1_verify(
+      &Sha256::digest(&signed_bytes),
+      permit.signature.signature.as_slice(),
+      pubkey,
+    )
+    .unwrap_or(false);
+  if !verified {
+    return Err(StdError::generic_err(INVALID_PERMIT));
+  }
+
+  let signer = pubkey_to_address(pubkey);
+  let revoked = ReadonlyPrefixedStorage::new(REVOKED_PERMITS, &deps.storage);
+  if revoked
+    .get(&revoked_permit_key(&signer, &permit.params.permit_name))
+    .is_some()
+  {
+    return Err(StdError::generic_err(PERMIT_REVOKED));
+  }
+  Ok(signer)
+}
+
+pub fn revoke_permit<S: Storage>(storage: &mut S, addr: &HumanAddr, permit_name: &str) {
+  let mut revoked = PrefixedStorage::new(REVOKED_PERMITS, storage);
+  revoked.set(&revoked_permit_key(addr, permit_name), &[1]);
+}
+
+fn revoked_permit_key(addr: &HumanAddr, permit_name: &str) -> Vec<u8> {
+  let mut key = addr.as_str().as_bytes().to_vec();
+  key.push(b'/');
+  key.extend(permit_name.as_bytes());
+  key
+}
@@ -0,0 +1,261 @@
+use cosmwasm_std::{HumanAddr, ReadonlyStorage, StdError, StdResult, Storage};
+use cosmwasm_storage::{PrefixedStorage, ReadonlyPrefixedStorage};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{OUTDATED_SCHEMA, SCHEMA_VERSION};
+use crate::game_state::{
+  Card, Currency, Dictionary, GameBoard, HandRecord, Player, State, TableConfig,
+};
+
+static VERSION_KEY: &[u8] = b"version";
+pub(crate) static CONFIG_KEY: &[u8] = b"config";
+pub(crate) static BOARD_KEY: &[u8] = b"board";
+pub(crate) static DECK_KEY: &[u8] = b"deck";
+static SEATS_KEY: &[u8] = b"seats";
+static PLAYERS: &[u8] = b"players";
+static HISTORY_INDEX_KEY: &[u8] = b"history_index";
+static HISTORY: &[u8] = b"hand_history";
+
+// table settings, only written when the table is created or its admin changes them
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Config {
+  pub started_time: u64,
+  pub level_design: u64,
+  pub password: Option<String>,
+  pub stamp_hash: String,
+  pub stamp_addr: HumanAddr,
+  pub min_buy: u64,
+  pub max_buy: u64,
+  pub jackpot_addr: HumanAddr,
+  pub jackpot_hash: String,
+  pub contract_addr: HumanAddr,
+  pub table: TableConfig,
+  pub currency: Currency,
+  pub dictionary: Dictionary,
+  pub admin: Option<HumanAddr>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Board {
+  can_join: bool,
+  game_board: GameBoard,
+  winner: Option<HumanAddr>,
+}
+
+// records are keyed by the order they were archived in, the turn of the last one tells
+// whether the hand being saved was already archived
+#[derive(Serialize, Deserialize, Default)]
+struct HistoryIndex {
+  len: u32,
+  last_turn: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Deck {
+  deck: Vec<Card>,
+  discard_pile: Vec<Card>,
+}
+
+pub fn load_state<S: ReadonlyStorage>(storage: &S) -> StdResult<State> {
+  if load_schema_version(storage)? != Some(SCHEMA_VERSION) {
+    return Err(StdError::generic_err(OUTDATED_SCHEMA));
+  }
+  let config = load_config(storage)?;
+  let board: Board = load(storage, BOARD_KEY)?;
+  let deck: Deck = load(storage, DECK_KEY)?;
+
+  let mut players = vec![];
+  for addr in load_seats(storage)? {
+    match load_player(storage, &addr)? {
+      Some(player) => players.push(player),
+      None => return Err(StdError::not_found("Player")),
+    }
+  }
+
+  Ok(State {
+    players,
+    can_join: board.can_join,
+    game_board: board.game_board,
+    winner: board.winner,
+    deck: deck.deck,
+    discard_pile: deck.discard_pile,
+    started_time: config.started_time,
+    level_design: config.level_design,
+    password: config.password,
+    stamp_hash: config.stamp_hash,
+    stamp_addr: config.stamp_addr,
+    min_buy: config.min_buy,
+    max_buy: config.max_buy,
+    jackpot_addr: config.jackpot_addr,
+    jackpot_hash: config.jackpot_hash,
+    contract_addr: config.contract_addr,
+    table: config.table,
+    currency: config.currency,
+    dictionary: config.dictionary,
+    admin: config.admin,
+  })
+}
+
+pub fn save_state<S: Storage>(storage: &mut S, state: &State) -> StdResult<()> {
+  save_if_changed(storage, VERSION_KEY, &SCHEMA_VERSION)?;
+  save_if_changed(
+    storage,
+    CONFIG_KEY,
+    &Config {
+      started_time: state.started_time,
+      level_design: state.level_design,
+      password: state.password.clone(),
+      stamp_hash: state.stamp_hash.clone(),
+      stamp_addr: state.stamp_addr.clone(),
+      min_buy: state.min_buy,
+      max_buy: state.max_buy,
+      jackpot_addr: state.jackpot_addr.clone(),
+      jackpot_hash: state.jackpot_hash.clone(),
+      contract_addr: state.contract_addr.clone(),
+      table: state.table.clone(),
+      currency: state.currency.clone(),
+      dictionary: state.dictionary.clone(),
+      admin: state.admin.clone(),
+    },
+  )?;
+  save_if_changed(
+    storage,
+    BOARD_KEY,
+    &Board {
+      can_join: state.can_join,
+      game_board: state.game_board.clone(),
+      winner: state.winner.clone(),
+    },
+  )?;
+  save_if_changed(
+    storage,
+    DECK_KEY,
+    &Deck {
+      deck: state.deck.clone(),
+      discard_pile: state.discard_pile.clone(),
+    },
+  )?;
+
+  let seats: Vec<HumanAddr> = state.players.iter().map(|p| p.addr.clone()).collect();
+  let previous_seats = if storage.get(SEATS_KEY).is_some() {
+    load_seats(storage)?
+  } else {
+    vec![]
+  };
+  let mut player_storage = PrefixedStorage::new(PLAYERS, storage);
+  for addr in previous_seats.iter() {
+    if !seats.contains(addr) {
+      player_storage.remove(addr.as_str().as_bytes());
+    }
+  }
+  for player in state.players.iter() {
+    save_if_changed(&mut player_storage, player.addr.as_str().as_bytes(), player)?;
+  }
+  save_if_changed(storage, SEATS_KEY, &seats)?;
+
+  if state.game_board.hand_log.is_settled() {
+    archive_hand(storage, &state.game_board.hand_log)?;
+  }
+  Ok(())
+}
+
+// appends a settled hand to the history, saving it again only updates the same record
+fn archive_hand<S: Storage>(storage: &mut S, record: &HandRecord) -> StdResult<()> {
+  let mut index = load_history_index(storage)?;
+  if index.last_turn != Some(record.turn) {
+    index.len += 1;
+    index.last_turn = Some(record.turn);
+    save_if_changed(storage, HISTORY_INDEX_KEY, &index)?;
+  }
+  let mut history_storage = PrefixedStorage::new(HISTORY, storage);
+  save_if_changed(&mut history_storage, &(index.len - 1).to_be_bytes(), record)
+}
+
+fn load_history_index<S: ReadonlyStorage>(storage: &S) -> StdResult<HistoryIndex> {
+  match storage.get(HISTORY_INDEX_KEY) {
+    Some(bytes) => deserialize(&bytes),
+    None => Ok(HistoryIndex::default()),
+  }
+}
+
+// hands archived so far, tables migrated mid-game only have the ones settled since
+pub fn load_hand_history_len<S: ReadonlyStorage>(storage: &S) -> StdResult<u32> {
+  Ok(load_history_index(storage)?.len)
+}
+
+pub fn load_hand_history<S: ReadonlyStorage>(
+  storage: &S,
+  start: u32,
+  limit: u32,
+) -> StdResult<Vec<HandRecord>> {
+  let end = load_hand_history_len(storage)?.min(start.saturating_add(limit));
+  let history_storage = ReadonlyPrefixedStorage::new(HISTORY, storage);
+  let mut records = vec![];
+  for i in start..end {
+    if let Some(bytes) = history_storage.get(&i.to_be_bytes()) {
+      records.push(deserialize(&bytes)?);
+    }
+  }
+  Ok(records)
+}
+
+// None for tables that still keep everything in the legacy json blob
+pub fn load_schema_version<S: ReadonlyStorage>(storage: &S) -> StdResult<Option<u8>> {
+  match storage.get(VERSION_KEY) {
+    Some(bytes) => Ok(Some(deserialize(&bytes)?)),
+    None => Ok(None),
+  }
+}
+
+pub fn load_config<S: ReadonlyStorage>(storage: &S) -> StdResult<Config> {
+  load(storage, CONFIG_KEY)
+}
+
+// addresses of the seated players, in seat order
+pub fn load_seats<S: ReadonlyStorage>(storage: &S) -> StdResult<Vec<HumanAddr>> {
+  load(storage, SEATS_KEY)
+}
+
+pub fn load_player<S: ReadonlyStorage>(storage: &S, addr: &HumanAddr) -> StdResult<Option<Player>> {
+  load_player_record(storage, addr)
+}
+
+// a player in whatever layout it was stored in, migrations read the older ones
+pub(crate) fn load_player_record<S: ReadonlyStorage, T: DeserializeOwned>(
+  storage: &S,
+  addr: &HumanAddr,
+) -> StdResult<Option<T>> {
+  let player_storage = ReadonlyPrefixedStorage::new(PLAYERS, storage);
+  match player_storage.get(addr.as_str().as_bytes()) {
+    Some(bytes) => Ok(Some(deserialize(&bytes)?)),
+    None => Ok(None),
+  }
+}
+
+pub(crate) fn load<S: ReadonlyStorage, T: DeserializeOwned>(
+  storage: &S,
+  key: &[u8],
+) -> StdResult<T> {
+  match storage.get(key) {
+    Some(bytes) => deserialize(&bytes),
+    None => Err(StdError::not_found(String::from_utf8_lossy(key))),
+  }
+}
+
+// every action rewrites the whole game, so untouched records are skipped to save gas on writes
+fn save_if_changed<S: Storage, T: Serialize>(
+  storage: &mut S,
+  key: &[u8],
+  value: &T,
+) -> StdResult<()> {
+  let bytes = bincode2::serialize(value).map_err(|e| StdError::serialize_err("bincode2", e))?;
+  if storage.get(key).as_ref() != Some(&bytes) {
+    storage.set(key, &bytes);
+  }
+  Ok(())
+}
+
+fn deserialize<T: DeserializeOwned>(bytes: &[u8]) -> StdResult<T> {
+  bincode2::deserialize(bytes).map_err(|e| StdError::parse_err("bincode2", e))
+}
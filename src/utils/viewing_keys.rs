@@ -0,0 +1,36 @@
+use cosmwasm_std::{Env, HumanAddr, ReadonlyStorage, StdError, StdResult, Storage};
+use cosmwasm_storage::{PrefixedStorage, ReadonlyPrefixedStorage};
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+use crate::constants::WRONG_VIEWING_KEY;
+
+pub const VIEWING_KEY_PREFIX: &str = "api_key_";
+static VIEWING_KEYS: &[u8] = b"viewing_keys";
+
+pub fn new_viewing_key(env: &Env, entropy: &str) -> String {
+  let mut seed: Vec<u8> = env.block.time.to_be_bytes().to_vec();
+  seed.extend(&env.block.height.to_be_bytes());
+  seed.extend(env.message.sender.as_str().as_bytes());
+  seed.extend(entropy.as_bytes());
+  let key: [u8; 32] = Sha256::digest(&seed).into();
+  format!("{}{}", VIEWING_KEY_PREFIX, base64::encode(key))
+}
+
+pub fn set_viewing_key<S: Storage>(storage: &mut S, addr: &HumanAddr, key: &str) {
+  let mut keys = PrefixedStorage::new(VIEWING_KEYS, storage);
+  keys.set(addr.as_str().as_bytes(), &Sha256::digest(key.as_bytes()));
+}
+
+pub fn check_viewing_key<S: ReadonlyStorage>(
+  storage: &S,
+  addr: &HumanAddr,
+  key: &str,
+) -> StdResult<()> {
+  let keys = ReadonlyPrefixedStorage::new(VIEWING_KEYS, storage);
+  let hashed_key = Sha256::digest(key.as_bytes());
+  match keys.get(addr.as_str().as_bytes()) {
+    Some(stored) if bool::from(stored.as_slice().ct_eq(&hashed_key[..])) => Ok(()),
+    _ => Err(StdError::generic_err(WRONG_VIEWING_KEY)),
+  }
+}
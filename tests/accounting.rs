@@ -0,0 +1,98 @@
+mod utils;
+
+#[cfg(test)]
+#[allow(unused_must_use)]
+mod test {
+  use cosmwasm_std::testing::{mock_env, MockApi, MockQuerier, MockStorage};
+  use cosmwasm_std::{BankMsg, Coin, CosmosMsg, Extern, HandleResult, Uint128, WasmMsg};
+
+  use secret_dreamscape::contract::{handle, HandleMsg};
+
+  use crate::utils::*;
+
+  // funds sent out by the contract, payouts and jackpot funding alike
+  fn sent_out(result: HandleResult) -> u128 {
+    let mut amount = 0;
+    for msg in result.unwrap().messages {
+      match msg {
+        CosmosMsg::Bank(BankMsg::Send { amount: coins, .. }) => {
+          amount += coins.iter().map(|c| c.amount.u128()).sum::<u128>()
+        }
+        CosmosMsg::Wasm(WasmMsg::Execute { send, .. }) => {
+          amount += send.iter().map(|c| c.amount.u128()).sum::<u128>()
+        }
+        _ => {}
+      }
+    }
+    amount
+  }
+
+  fn assert_accounts_balance(
+    deps: &mut Extern<MockStorage, MockApi, MockQuerier>,
+    player: u64,
+    deposited: u128,
+    sent: u128,
+  ) {
+    let game = get_game_state(deps, player);
+    let chips: u128 = game.players.iter().map(|p| p.chips as u128).sum();
+    assert_eq!(
+      chips + game.pool as u128 + sent,
+      deposited,
+      "Chips, pool and funds sent out don't add up to the deposits"
+    );
+  }
+
+  /// Test if every deposited chip is always either held by a player, in the pool or sent out
+  #[test]
+  fn chips_pool_and_payouts_add_up_to_deposits() {
+    let (_, mut deps) = init_with_2_players(false);
+    let deposited = 20_000_000;
+    let mut sent = 0;
+    assert_accounts_balance(&mut deps, 0, deposited, sent);
+
+    send_bet(&mut deps, 0, Uint128(2_000_000));
+    send_bet(&mut deps, 1, Uint128(1_000_000));
+    raise(&mut deps, 1, Uint128(3_000_000));
+    let overpaid = match_bet(&mut deps, 0, Uint128(3_000_000));
+    assert!(overpaid.is_err(), "Match charged more than the call");
+    match_bet(&mut deps, 0, Uint128(2_000_000));
+    assert_accounts_balance(&mut deps, 0, deposited, sent);
+
+    send_bet(&mut deps, 0, Uint128(1_000_001));
+    send_bet(&mut deps, 1, Uint128(1_000_001));
+    assert_accounts_balance(&mut deps, 0, deposited, sent);
+
+    // p0: y, t, g, c, l
+    // p1: r, t, i, a, d
+    // river: l, i, n, a, b
+    put_down_word(&mut deps, 0, vec![254, 251, 250, 4, 0, 3, 253, 252]); // billycan: 15
+    sent += sent_out(put_down_word(&mut deps, 1, vec![254, 253, 252, 4, 2, 1])); // bandit: 9
+    assert_accounts_balance(&mut deps, 0, deposited, sent);
+
+    // the button moved to player0, so player1 opens the next hand
+    request_next_turn(&mut deps, 0);
+    send_bet(&mut deps, 1, Uint128(3_000_000));
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    sent += sent_out(fold(&mut deps, 0));
+    assert_accounts_balance(&mut deps, 1, deposited, sent);
+
+    sent += sent_out(leave(&mut deps, 0));
+    assert_accounts_balance(&mut deps, 1, deposited, sent);
+  }
+
+  /// Test if funds sent along with an action other than BuyChips are rejected instead of kept
+  #[test]
+  fn funds_are_only_taken_by_buy_chips() {
+    let (_, mut deps) = init_with_2_players(false);
+
+    let bet_with_funds = handle(
+      &mut deps,
+      mock_env("player0", &[Coin::new(1_000_000, "uscrt")]),
+      HandleMsg::Bet { amount: 1_000_000 },
+    );
+    assert!(
+      bet_with_funds.is_err(),
+      "Funds sent with a bet were accepted"
+    );
+  }
+}
@@ -1,8 +1,9 @@
 mod utils;
 
 #[cfg(test)]
+#[allow(unused_must_use)]
 mod test {
-  use cosmwasm_std::{BankMsg, Coin, CosmosMsg, HumanAddr, Uint128};
+  use cosmwasm_std::{Coin, CosmosMsg, HumanAddr, Uint128, WasmMsg};
 
   use secret_dreamscape::game_state::GameRound;
 
@@ -146,6 +147,54 @@ mod test {
     assert!(bet.is_err(), "Player can bet after folding");
   }
 
+  /// Test if a player who put down a word can't fold out of the showdown
+  #[test]
+  fn player_cant_fold_after_putting_down_a_word() {
+    let mut deps = init_with_chips(vec![5_000_000, 5_000_000, 5_000_000]);
+    for _ in 0..2 {
+      for i in 0..3 {
+        send_bet(&mut deps, i, Uint128(1_000_000));
+      }
+    }
+
+    put_down_word(&mut deps, 0, vec![0]);
+    let fold_result = fold(&mut deps, 0);
+    assert!(
+      fold_result.is_err(),
+      "Player folded after putting down a word"
+    );
+
+    put_down_word(&mut deps, 1, vec![0]);
+    let game = get_game_state(&mut deps, 0);
+    assert!(
+      game.winner.is_none(),
+      "Showdown was settled before everyone put down a word"
+    );
+  }
+
+  /// Test if folding in the choice round settles the showdown once everyone else is down
+  #[test]
+  fn fold_in_choice_settles_the_showdown() {
+    let mut deps = init_with_chips(vec![5_000_000, 5_000_000, 5_000_000]);
+    for _ in 0..2 {
+      for i in 0..3 {
+        send_bet(&mut deps, i, Uint128(1_000_000));
+      }
+    }
+
+    put_down_word(&mut deps, 0, vec![0]);
+    put_down_word(&mut deps, 1, vec![0]);
+    fold(&mut deps, 2).unwrap();
+    let game = get_game_state(&mut deps, 0);
+    assert!(game.winner.is_some(), "Showdown wasn't settled");
+
+    let put_down = put_down_word(&mut deps, 2, vec![0]);
+    assert!(
+      put_down.is_err(),
+      "Word was put down after the hand was over"
+    );
+  }
+
   /// Test if a player can't send a bet smaller than 1 SCRT
   #[test]
   fn cant_send_small_bet() {
@@ -300,50 +349,37 @@ mod test {
     put_down_word(&mut deps, 0, vec![0]); // y: 0
     let final_step = (put_down_word(&mut deps, 1, vec![0])).unwrap(); // r: 0
 
-    let first_transfer_message = final_step.clone().messages[0].clone();
-    let second_transfer_message = final_step.messages[1].clone();
-
-    match (first_transfer_message, second_transfer_message) {
-      (CosmosMsg::Bank(msg1), CosmosMsg::Bank(msg2)) => match (msg1, msg2) {
-        (
-          BankMsg::Send {
-            from_address: _from_address1,
-            to_address: to_address1,
-            amount: amount1,
-          },
-          BankMsg::Send {
-            from_address: _from_address2,
-            to_address: to_address2,
-            amount: amount2,
-          },
-        ) => {
-          assert_eq!(
-            to_address1,
-            HumanAddr("player0".to_string()),
-            "First Winner did not collect the pot"
-          );
-          assert_eq!(
-            amount1[0],
-            Coin {
-              amount: Uint128(2_000_000),
-              denom: "uscrt".to_string(),
-            }
-          );
-          assert_eq!(
-            to_address2,
-            HumanAddr("player1".to_string()),
-            "Second Winner did not collect the pot"
-          );
-          assert_eq!(
-            amount2[0],
-            Coin {
-              amount: Uint128(2_000_000),
-              denom: "uscrt".to_string(),
-            }
-          );
-        }
-      },
-      _ => panic!("Expected bank transfer message"),
+    let game = get_game_state(&mut deps, 0);
+    // each player put 2 SCRT in the pot, the 4 SCRT pot minus the 10% rake is split in two
+    assert_eq!(
+      game.players[0].chips, 9_800_000,
+      "First Winner did not collect the pot"
+    );
+    assert_eq!(
+      game.players[1].chips, 9_800_000,
+      "Second Winner did not collect the pot"
+    );
+
+    match final_step.messages[0].clone() {
+      CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr,
+        send,
+        ..
+      }) => {
+        assert_eq!(
+          contract_addr,
+          HumanAddr("jackpot".to_string()),
+          "Rake was not sent to the jackpot"
+        );
+        assert_eq!(
+          send[0],
+          Coin {
+            amount: Uint128(400_000),
+            denom: "uscrt".to_string(),
+          }
+        );
+      }
+      _ => panic!("Expected jackpot funding message"),
     }
   }
 }
@@ -0,0 +1,175 @@
+mod utils;
+
+#[cfg(test)]
+#[allow(unused_must_use)]
+mod test {
+  use cosmwasm_std::testing::{mock_env, MockApi, MockQuerier, MockStorage};
+  use cosmwasm_std::{Extern, HandleResult, Uint128};
+  use rand_chacha::rand_core::SeedableRng;
+  use rand_chacha::ChaChaRng;
+
+  use secret_dreamscape::contract::{handle, HandleMsg};
+  use secret_dreamscape::game_state::{Card, Dictionary, TableConfig};
+  use secret_dreamscape::utils::cards::{generate_deck, get_score_for_word};
+
+  use crate::utils::*;
+
+  // a deck of only a's and blanks, bet through to the round words are put down in
+  fn init_with_blanks() -> Extern<MockStorage, MockApi, MockQuerier> {
+    let mut msg = default_init_msg(false);
+    msg.table = Some(TableConfig {
+      gold_cards: 0,
+      ..TableConfig::default()
+    });
+    msg.dictionary = Some(Dictionary {
+      distribution: vec![(0, 30)],
+      blanks: 30,
+      ..Dictionary::default()
+    });
+    let (_, mut deps) = init_with_n_players_and_msg(2, msg);
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    send_bet(&mut deps, 1, Uint128(1_000_000));
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    send_bet(&mut deps, 1, Uint128(1_000_000));
+    deps
+  }
+
+  // indexes of a blank and of an a that player 0 can put down
+  fn blank_and_a(deps: &mut Extern<MockStorage, MockApi, MockQuerier>) -> (u8, u8) {
+    let game = get_game_state(deps, 0);
+    let mut cards: Vec<(u8, Card)> = vec![];
+    for (i, card) in game.hand.iter().enumerate() {
+      cards.push((i as u8, card.clone()));
+    }
+    for (i, card) in game.river.unwrap().iter().enumerate() {
+      cards.push((250 + i as u8, card.clone()));
+    }
+    let blank = cards.iter().find(|(_, c)| c.blank).unwrap().0;
+    let a = cards.iter().find(|(_, c)| !c.blank).unwrap().0;
+    (blank, a)
+  }
+
+  fn put_down_with_blanks(
+    deps: &mut Extern<MockStorage, MockApi, MockQuerier>,
+    player: usize,
+    word: Vec<u8>,
+    blank_letters: Vec<u8>,
+  ) -> HandleResult {
+    handle(
+      deps,
+      mock_env(format!("player{}", player), &[]),
+      HandleMsg::PutDownCard {
+        indexes: word,
+        opened_dictionary: false,
+        proof: None,
+        blank_letters,
+      },
+    )
+  }
+
+  /// Test if a blank scores no points but counts as its letter for the dictionary
+  #[test]
+  fn blank_scores_nothing() {
+    let mut word = vec![
+      Card {
+        letter: 2,
+        gold: false,
+        blank: false,
+      },
+      Card {
+        letter: 0,
+        gold: false,
+        blank: false,
+      },
+      Card {
+        letter: 19,
+        gold: false,
+        blank: false,
+      },
+    ];
+    assert_eq!(get_score_for_word(&word, &Dictionary::default()), 5);
+    word[1].blank = true;
+    assert_eq!(
+      get_score_for_word(&word, &Dictionary::default()),
+      4,
+      "Blank was worth points"
+    );
+  }
+
+  /// Test if a gold blank is still worth nothing and doesn't double the word
+  #[test]
+  fn gold_blank_doesnt_double() {
+    let mut word = vec![
+      Card {
+        letter: 2,
+        gold: false,
+        blank: false,
+      },
+      Card {
+        letter: 0,
+        gold: true,
+        blank: true,
+      },
+      Card {
+        letter: 19,
+        gold: false,
+        blank: false,
+      },
+    ];
+    assert_eq!(
+      get_score_for_word(&word, &Dictionary::default()),
+      4,
+      "Gold blank doubled the word"
+    );
+    word[0].gold = true;
+    assert_eq!(get_score_for_word(&word, &Dictionary::default()), 8);
+  }
+
+  /// Test if the default deck has the 2 blanks of scrabble
+  #[test]
+  fn default_deck_has_blanks() {
+    assert_eq!(Dictionary::default().blanks, 2);
+    let deck = generate_deck(ChaChaRng::from_seed([0; 32]), 0, &Dictionary::default());
+    assert_eq!(deck.len(), 100, "Default deck isn't a scrabble set");
+    assert_eq!(
+      deck.iter().filter(|c| c.blank).count(),
+      2,
+      "Blanks weren't dealt"
+    );
+  }
+
+  /// Test if blanks are dealt and take the letter they're given when put down
+  #[test]
+  fn blank_takes_the_given_letter() {
+    let mut deps = init_with_blanks();
+    let (blank, a) = blank_and_a(&mut deps);
+
+    // ba
+    let result = put_down_with_blanks(&mut deps, 0, vec![blank, a], vec![1]);
+    assert!(result.is_ok(), "Word with a blank was rejected");
+    put_down_word(&mut deps, 1, vec![]);
+
+    let game = get_game_state(&mut deps, 0);
+    let word = game.words[0].word.clone().unwrap();
+    assert_eq!(word.cards[0].letter, 1, "Blank didn't take its letter");
+    assert!(word.cards[0].blank, "Blank lost its flag");
+    assert_eq!(game.words[0].points, 1, "Blank was worth points");
+  }
+
+  /// Test if every blank in the word needs exactly one letter of the alphabet
+  #[test]
+  fn blank_letters_must_match() {
+    let mut deps = init_with_blanks();
+    let (blank, a) = blank_and_a(&mut deps);
+
+    let result = put_down_with_blanks(&mut deps, 0, vec![blank, a], vec![]);
+    assert!(result.is_err(), "Blank without a letter was accepted");
+    let result = put_down_with_blanks(&mut deps, 0, vec![blank, a], vec![1, 1]);
+    assert!(result.is_err(), "Letter without a blank was accepted");
+    let result = put_down_with_blanks(&mut deps, 0, vec![blank, a], vec![26]);
+    assert!(result.is_err(), "Blank outside the alphabet was accepted");
+
+    let result = put_down_with_blanks(&mut deps, 0, vec![blank, a], vec![1]);
+    assert!(result.is_ok(), "Word couldn't be put down after a bad one");
+  }
+}
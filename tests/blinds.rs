@@ -0,0 +1,134 @@
+mod utils;
+
+#[cfg(test)]
+#[allow(unused_must_use)]
+mod test {
+  use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage};
+  use cosmwasm_std::{Extern, Uint128};
+
+  use secret_dreamscape::game_state::{GameRound, PlayerAction, TableConfig};
+
+  use crate::utils::*;
+
+  fn init_with_forced_bets(
+    chips: Vec<u128>,
+    small_blind: u64,
+    big_blind: u64,
+    ante: u64,
+  ) -> Extern<MockStorage, MockApi, MockQuerier> {
+    let mut msg = default_init_msg(false);
+    msg.table = Some(TableConfig {
+      small_blind,
+      big_blind,
+      ante,
+      ..TableConfig::default()
+    });
+    init_with_chips_and_msg(chips, msg)
+  }
+
+  /// Test if the two players after the button post the blinds on the first hand and the rest
+  /// has to match them
+  #[test]
+  fn blinds_are_posted_on_the_first_hand() {
+    let mut deps =
+      init_with_forced_bets(vec![5_000_000, 5_000_000, 5_000_000], 250_000, 500_000, 0);
+    let game = get_game_state(&mut deps, 0);
+    // the button starts on the last seat
+    assert_eq!(game.dealer, 2);
+    assert_eq!(game.pool, 750_000, "Blinds weren't put in the pool");
+    assert_eq!(game.players[0].chips, 4_750_000, "Small blind wasn't taken");
+    assert_eq!(game.players[1].chips, 4_500_000, "Big blind wasn't taken");
+    assert!(game.players[0].last_action == Some(PlayerAction::PostedBlind(250_000)));
+    assert!(game.players[1].last_action == Some(PlayerAction::PostedBlind(500_000)));
+    assert_eq!(
+      player_on_action(&mut deps),
+      Some(2),
+      "Action didn't start after the blinds"
+    );
+
+    send_bet(&mut deps, 2, Uint128(500_000));
+    let game = get_game_state(&mut deps, 0);
+    assert!(
+      game.round == GameRound::Matching,
+      "Small blind didn't have to match"
+    );
+    assert_eq!(player_on_action(&mut deps), Some(0));
+    match_bet(&mut deps, 0, Uint128(250_000));
+    let game = get_game_state(&mut deps, 0);
+    assert!(game.round == GameRound::Flop, "Round didn't move on");
+    assert_eq!(game.pool, 1_500_000);
+  }
+
+  /// Test if the blinds move around the table with the button
+  #[test]
+  fn blinds_rotate_with_the_button() {
+    let mut deps = init_with_forced_bets(vec![5_000_000, 5_000_000], 250_000, 500_000, 0);
+    let game = get_game_state(&mut deps, 0);
+    // heads-up the dealer posts the big blind
+    assert_eq!(game.players[0].chips, 4_750_000, "Small blind wasn't taken");
+    assert_eq!(game.players[1].chips, 4_500_000, "Big blind wasn't taken");
+    assert!(
+      game.round == GameRound::Matching,
+      "Small blind didn't have to match"
+    );
+    assert_eq!(player_on_action(&mut deps), Some(0));
+
+    // player1 gets back the uncalled half of the big blind and wins the rest less the rake
+    fold(&mut deps, 0);
+    request_next_turn(&mut deps, 0).unwrap();
+    let game = get_game_state(&mut deps, 0);
+    assert_eq!(game.dealer, 0, "Button didn't move");
+    assert_eq!(game.players[1].chips, 4_950_000, "Small blind wasn't taken");
+    assert_eq!(game.players[0].chips, 4_250_000, "Big blind wasn't taken");
+    assert_eq!(player_on_action(&mut deps), Some(1));
+  }
+
+  /// Test if a player who can't cover their blind sits out and the blind passes to the next one
+  #[test]
+  fn player_who_cant_cover_the_blind_sits_out() {
+    let mut deps =
+      init_with_forced_bets(vec![5_000_000, 5_000_000, 7_000_000], 250_000, 6_000_000, 0);
+    let game = get_game_state(&mut deps, 0);
+    assert!(
+      game.players[1].folded,
+      "Player without the big blind wasn't sat out"
+    );
+    assert_eq!(game.players[1].chips, 5_000_000, "Player who sat out paid");
+    assert_eq!(game.players[2].chips, 1_000_000, "Big blind didn't pass on");
+    assert!(
+      game.round == GameRound::Matching,
+      "Small blind didn't have to match"
+    );
+    assert_eq!(player_on_action(&mut deps), Some(0));
+  }
+
+  /// Test if everyone dealt in pays the ante
+  #[test]
+  fn antes_are_taken_from_everyone() {
+    let mut deps = init_with_forced_bets(vec![5_000_000, 5_000_000, 5_000_000], 0, 0, 100_000);
+    let game = get_game_state(&mut deps, 0);
+    assert_eq!(game.pool, 300_000, "Antes weren't put in the pool");
+    for i in 0..3 {
+      assert_eq!(game.players[i].chips, 4_900_000, "Ante wasn't taken");
+    }
+    assert!(
+      game.round == GameRound::Blind,
+      "Antes settled the blind round"
+    );
+    assert_eq!(player_on_action(&mut deps), Some(0));
+  }
+
+  /// Test if the hand is won right away when everyone else had to sit out
+  #[test]
+  fn hand_ends_when_only_one_player_can_cover_the_blinds() {
+    let mut deps = init_with_forced_bets(vec![5_000_000, 8_000_000], 6_000_000, 6_000_000, 0);
+    let game = get_game_state(&mut deps, 0);
+    assert!(
+      game.players[0].folded,
+      "Player without the small blind wasn't sat out"
+    );
+    assert!(game.action_on.is_none(), "Action stayed on after the hand");
+    let next_turn = request_next_turn(&mut deps, 0);
+    assert!(next_turn.is_ok(), "Hand wasn't settled");
+  }
+}
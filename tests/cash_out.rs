@@ -0,0 +1,115 @@
+mod utils;
+
+#[cfg(test)]
+#[allow(unused_must_use)]
+mod test {
+  use cosmwasm_std::{BankMsg, Coin, CosmosMsg, HumanAddr, Uint128};
+
+  use crate::utils::*;
+
+  fn payouts(messages: Vec<CosmosMsg>) -> Vec<(HumanAddr, Coin)> {
+    let mut payouts = vec![];
+    for msg in messages {
+      if let CosmosMsg::Bank(BankMsg::Send {
+        to_address, amount, ..
+      }) = msg
+      {
+        payouts.push((to_address, amount[0].clone()));
+      }
+    }
+    payouts
+  }
+
+  /// Test if chips can only be cashed out between hands and the player keeps their seat
+  #[test]
+  fn cash_out_only_between_hands() {
+    let (_, mut deps) = init_with_2_players(false);
+
+    let mid_hand = cash_out(&mut deps, 0, 1_000_000);
+    assert!(
+      mid_hand.is_err(),
+      "Player cashed out in the middle of a hand"
+    );
+
+    fold(&mut deps, 0);
+
+    let too_much = cash_out(&mut deps, 0, 20_000_000);
+    assert!(too_much.is_err(), "Player cashed out more than they had");
+
+    let cash_out = cash_out(&mut deps, 0, 1_000_000).unwrap();
+    assert_eq!(
+      payouts(cash_out.messages),
+      vec![(
+        HumanAddr("player0".to_string()),
+        Coin::new(1_000_000, "uscrt")
+      )]
+    );
+    let game = get_game_state(&mut deps, 0);
+    assert_eq!(game.players.len(), 2, "Player lost their seat");
+    assert_eq!(game.players[0].chips, 9_000_000, "Chips weren't taken off");
+  }
+
+  /// Test if stacks can be topped up to the maximum buy, but not with chips already in the pot
+  #[test]
+  fn top_ups_stay_within_max_buy() {
+    let mut deps = init_with_chips(vec![5_000_000, 5_000_000, 5_000_000]);
+
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    let after_bet = buy_chips(&mut deps, 0, Uint128(1_000_000));
+    assert!(after_bet.is_err(), "Player topped up after betting");
+
+    let before_bet = buy_chips(&mut deps, 1, Uint128(500_000));
+    assert!(before_bet.is_ok(), "Player couldn't top up before betting");
+    let over_max = buy_chips(&mut deps, 1, Uint128(5_000_000));
+    assert!(over_max.is_err(), "Player topped up past the maximum buy");
+
+    fold(&mut deps, 1);
+    fold(&mut deps, 2);
+
+    let between_hands = buy_chips(&mut deps, 0, Uint128(500_000));
+    assert!(
+      between_hands.is_ok(),
+      "Player couldn't top up between hands"
+    );
+    let game = get_game_state(&mut deps, 0);
+    // nobody called the 1_000_000 bet, so it comes back without rake
+    assert_eq!(game.players[0].chips, 5_500_000);
+    assert_eq!(game.players[1].chips, 5_500_000);
+  }
+
+  /// Test if the stack of a player who leaves mid-hand is paid out once the hand is settled
+  #[test]
+  fn leaving_mid_hand_is_settled_after_the_hand() {
+    let mut deps = init_with_chips(vec![5_000_000, 5_000_000, 5_000_000]);
+    for i in 0..3 {
+      send_bet(&mut deps, i, Uint128(1_000_000));
+    }
+
+    let leave = leave(&mut deps, 2).unwrap();
+    assert!(
+      payouts(leave.messages).is_empty(),
+      "Chips were paid out in the middle of the hand"
+    );
+    let game = get_game_state(&mut deps, 0);
+    assert_eq!(
+      game.pending_withdrawals.len(),
+      1,
+      "Withdrawal wasn't recorded"
+    );
+    assert_eq!(game.pending_withdrawals[0].amount, 4_000_000);
+
+    let fold = fold(&mut deps, 0).unwrap();
+    assert!(
+      payouts(fold.messages).contains(&(
+        HumanAddr("player2".to_string()),
+        Coin::new(4_000_000, "uscrt")
+      )),
+      "Withdrawal wasn't paid out after the hand"
+    );
+    let game = get_game_state(&mut deps, 0);
+    assert!(
+      game.pending_withdrawals.is_empty(),
+      "Withdrawal was left pending"
+    );
+  }
+}
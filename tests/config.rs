@@ -0,0 +1,188 @@
+mod utils;
+
+#[cfg(test)]
+#[allow(unused_must_use)]
+mod test {
+  use cosmwasm_std::{CosmosMsg, HumanAddr, Uint128, WasmMsg};
+
+  use secret_dreamscape::game_state::{DamageRule, InvalidWordPolicy, TableConfig};
+
+  use crate::utils::*;
+
+  // block time used by mock_env for every action after joining
+  const NOW: u64 = 1_571_797_419;
+
+  fn small_table() -> TableConfig {
+    TableConfig {
+      max_players: 2,
+      starting_hp: 3,
+      hand_size: 3,
+      river_size: 4,
+      gold_cards: 0,
+      rake_percentage: 5,
+      min_bet: 500_000,
+      small_blind: 0,
+      big_blind: 0,
+      ante: 0,
+      invalid_words: InvalidWordPolicy::Reject,
+      damage: DamageRule::default(),
+      turn_timeout: 60,
+      min_raise: None,
+      max_raises: 2,
+    }
+  }
+
+  /// Test if a table uses the rules it was created with
+  #[test]
+  fn custom_table_rules_are_used() {
+    let mut msg = default_init_msg(false);
+    msg.table = Some(small_table());
+    let (_, mut deps) = init_with_n_players_and_msg(2, msg);
+
+    assert!(
+      !get_join_permissions(&mut deps).can_join,
+      "Full table was reported as joinable"
+    );
+    let join_attempt = join(&mut deps, 2, 2, false);
+    assert!(
+      join_attempt.is_err(),
+      "Player was able to join a full table"
+    );
+
+    let game = get_game_state(&mut deps, 0);
+    assert_eq!(game.hand.len(), 3, "Hand size wasn't used");
+    assert_eq!(game.players[0].hp, 3, "Starting hp wasn't used");
+
+    let small_bet = send_bet(&mut deps, 0, Uint128(125_000));
+    assert!(
+      small_bet.is_err(),
+      "Bet below the table minimum was accepted"
+    );
+    let bet = send_bet(&mut deps, 0, Uint128(500_000));
+    assert!(bet.is_ok(), "Bet at the table minimum was rejected");
+
+    let game = get_game_state(&mut deps, 0);
+    assert_eq!(
+      game.round_deadline,
+      Some(NOW + 60),
+      "Turn timeout wasn't used"
+    );
+  }
+
+  /// Test if a hand on a table without rake is settled without funding the jackpot
+  #[test]
+  fn zero_rake_hand_is_settled() {
+    let mut msg = default_init_msg(false);
+    msg.table = Some(TableConfig {
+      rake_percentage: 0,
+      ..TableConfig::default()
+    });
+    let (_, mut deps) = init_with_n_players_and_msg(2, msg);
+
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    send_bet(&mut deps, 1, Uint128(1_000_000));
+    let settled = fold(&mut deps, 0).unwrap();
+    let jackpot = HumanAddr("jackpot".to_string());
+    assert!(
+      !settled.messages.iter().any(|m| match m {
+        CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => contract_addr == &jackpot,
+        _ => false,
+      }),
+      "Jackpot was funded with nothing"
+    );
+
+    let game = get_game_state(&mut deps, 1);
+    assert_eq!(
+      game.players[1].chips, 11_000_000,
+      "Whole pot wasn't paid out"
+    );
+  }
+
+  /// Test if tables with impossible rules can't be created
+  #[test]
+  fn invalid_table_config_is_rejected() {
+    let invalid_tables = vec![
+      TableConfig {
+        max_players: 1,
+        ..small_table()
+      },
+      TableConfig {
+        starting_hp: 0,
+        ..small_table()
+      },
+      TableConfig {
+        hand_size: 0,
+        ..small_table()
+      },
+      TableConfig {
+        river_size: 7,
+        ..small_table()
+      },
+      TableConfig {
+        max_players: 10,
+        hand_size: 10,
+        ..small_table()
+      },
+      TableConfig {
+        gold_cards: 101,
+        ..small_table()
+      },
+      TableConfig {
+        rake_percentage: 101,
+        ..small_table()
+      },
+      TableConfig {
+        min_bet: 0,
+        ..small_table()
+      },
+      TableConfig {
+        small_blind: 500_000,
+        big_blind: 250_000,
+        ..small_table()
+      },
+      TableConfig {
+        turn_timeout: 0,
+        ..small_table()
+      },
+      TableConfig {
+        min_raise: Some(0),
+        ..small_table()
+      },
+    ];
+    for table in invalid_tables {
+      let mut msg = default_init_msg(false);
+      msg.table = Some(table);
+      let (init_result, _) = init_with_n_players_and_msg(0, msg);
+      assert!(init_result.is_err(), "Invalid table config was accepted");
+    }
+  }
+
+  /// Test if the table config can be queried, with the defaults when none was given
+  #[test]
+  fn config_can_be_queried() {
+    let (_, mut deps) = init_with_n_players(0, true);
+    let config = get_config(&mut deps);
+    assert!(
+      config.table == TableConfig::default(),
+      "Default table config wasn't used"
+    );
+    assert_eq!(
+      config.min_raise, 125_000,
+      "Min raise didn't default to min bet"
+    );
+    assert!(config.requires_password, "Private table wasn't reported");
+
+    let mut msg = default_init_msg(false);
+    msg.table = Some(small_table());
+    let (_, mut deps) = init_with_n_players_and_msg(0, msg);
+    let config = get_config(&mut deps);
+    assert!(
+      config.table == small_table(),
+      "Custom table config wasn't returned"
+    );
+    assert_eq!(
+      config.min_raise, 500_000,
+      "Min raise didn't default to min bet"
+    );
+  }
+}
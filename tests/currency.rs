@@ -0,0 +1,160 @@
+mod utils;
+
+#[cfg(test)]
+#[allow(unused_must_use)]
+mod test {
+  use cosmwasm_std::testing::*;
+  use cosmwasm_std::{to_binary, BankMsg, Coin, CosmosMsg, HumanAddr, Uint128, WasmMsg};
+
+  use secret_dreamscape::contract::{handle, HandleMsg, ReceiveMsg};
+  use secret_dreamscape::game_state::Currency;
+
+  use crate::utils::*;
+
+  fn token() -> Currency {
+    Currency::Snip20 {
+      addr: HumanAddr("token".to_string()),
+      hash: "token_hash".to_string(),
+    }
+  }
+
+  fn receive_msg(from: &str, amount: u128) -> HandleMsg {
+    HandleMsg::Receive {
+      sender: HumanAddr(from.to_string()),
+      from: HumanAddr(from.to_string()),
+      amount: Uint128(amount),
+      memo: None,
+      msg: Some(to_binary(&ReceiveMsg::BuyChips {}).unwrap()),
+    }
+  }
+
+  /// Test if a table in another native denom only sells and pays out chips in that denom
+  #[test]
+  fn native_denom_table_uses_its_denom() {
+    let mut msg = default_init_msg(false);
+    msg.currency = Some(Currency::Native {
+      denom: "uatom".to_string(),
+    });
+    let (_, mut deps) = init_with_n_players_and_msg(2, msg);
+
+    let game = get_game_state(&mut deps, 0);
+    assert_eq!(game.players[0].chips, 0, "Chips were bought with uscrt");
+
+    let buy = handle(
+      &mut deps,
+      mock_env("player0", &[Coin::new(5_000_000, "uatom")]),
+      HandleMsg::BuyChips {},
+    );
+    assert!(buy.is_ok(), "Chips couldn't be bought in the table's denom");
+
+    let leave = leave(&mut deps, 0).unwrap();
+    match leave.messages.last().unwrap().clone() {
+      CosmosMsg::Bank(BankMsg::Send { amount, .. }) => {
+        assert_eq!(amount[0], Coin::new(5_000_000, "uatom"));
+      }
+      _ => panic!("Expected the chips to be paid out"),
+    }
+  }
+
+  /// Test if a snip20 table registers with the token and sells chips through Receive
+  #[test]
+  fn snip20_chips_are_bought_through_receive() {
+    let mut msg = default_init_msg(false);
+    msg.currency = Some(token());
+    let (init_result, mut deps) = init_with_n_players_and_msg(2, msg);
+
+    match init_result.unwrap().messages[1].clone() {
+      CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => {
+        assert_eq!(contract_addr, HumanAddr("token".to_string()));
+      }
+      _ => panic!("Expected the table to register with the token"),
+    }
+
+    let native_buy = buy_chips(&mut deps, 0, Uint128(5_000_000));
+    assert!(native_buy.is_err(), "Chips were bought with uscrt");
+
+    let fake_token = handle(
+      &mut deps,
+      mock_env("not_the_token", &[]),
+      receive_msg("player0", 5_000_000),
+    );
+    assert!(fake_token.is_err(), "Another token bought chips");
+
+    let stranger = handle(
+      &mut deps,
+      mock_env("token", &[]),
+      receive_msg("stranger", 5_000_000),
+    );
+    assert!(stranger.is_err(), "Someone outside the game bought chips");
+
+    let buy = handle(
+      &mut deps,
+      mock_env("token", &[]),
+      receive_msg("player0", 5_000_000),
+    );
+    assert!(buy.is_ok(), "Chips couldn't be bought with the token");
+    let game = get_game_state(&mut deps, 0);
+    assert_eq!(game.players[0].chips, 5_000_000, "Chips weren't credited");
+  }
+
+  /// Test if a player who left the table can't buy chips through Receive
+  #[test]
+  fn snip20_chips_arent_sold_to_players_who_left() {
+    let mut msg = default_init_msg(false);
+    msg.currency = Some(token());
+    let (_, mut deps) = init_with_n_players_and_msg(2, msg);
+    // the hand is only dealt once both bought in with the token
+    for i in 0..2 {
+      handle(
+        &mut deps,
+        mock_env("token", &[]),
+        receive_msg(&format!("player{}", i), 5_000_000),
+      );
+    }
+
+    leave(&mut deps, 0).unwrap();
+    let game = get_game_state(&mut deps, 1);
+    assert!(game.players[0].left, "Player didn't leave mid-hand");
+
+    let buy = handle(
+      &mut deps,
+      mock_env("token", &[]),
+      receive_msg("player0", 5_000_000),
+    );
+    assert!(buy.is_err(), "Player who left bought chips");
+  }
+
+  /// Test if a snip20 table pays out chips with a transfer on the token
+  #[test]
+  fn snip20_chips_are_paid_out_with_transfer() {
+    let mut msg = default_init_msg(false);
+    msg.currency = Some(token());
+    let (_, mut deps) = init_with_n_players_and_msg(2, msg);
+    handle(
+      &mut deps,
+      mock_env("token", &[]),
+      receive_msg("player0", 5_000_000),
+    );
+
+    let leave = leave(&mut deps, 0).unwrap();
+    match leave.messages.last().unwrap().clone() {
+      CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr,
+        msg,
+        send,
+        ..
+      }) => {
+        assert_eq!(contract_addr, HumanAddr("token".to_string()));
+        assert!(send.is_empty(), "Native funds were sent with the transfer");
+        let transfer = String::from_utf8(msg.0).unwrap();
+        assert!(
+          transfer.starts_with("{\"transfer\":"),
+          "Expected a transfer"
+        );
+        assert!(transfer.contains("\"recipient\":\"player0\""));
+        assert!(transfer.contains("\"amount\":\"5000000\""));
+      }
+      _ => panic!("Expected a token transfer"),
+    }
+  }
+}
@@ -0,0 +1,29 @@
+mod utils;
+
+#[cfg(test)]
+#[allow(unused_must_use)]
+mod test {
+  use crate::utils::*;
+
+  /// Test if played and folded cards are shuffled back in so the deck never runs dry
+  #[test]
+  fn deck_is_reshuffled_when_it_runs_low() {
+    let (_, mut deps) = init_with_2_players(false);
+
+    // every folded hand draws 10 cards, so the first deck would be gone after 9 hands
+    for i in 0..30 {
+      let folded = fold(&mut deps, i % 2);
+      assert!(folded.is_ok(), "Folding failed in hand {}", i);
+      let next_turn = request_next_turn(&mut deps, 0);
+      assert!(
+        next_turn.is_ok(),
+        "Next hand couldn't be dealt in hand {}",
+        i
+      );
+    }
+
+    let game = get_game_state(&mut deps, 0);
+    assert_eq!(game.hand.len(), 5, "Hand wasn't refilled");
+    assert_eq!(game.turn, 30, "Not every hand was played");
+  }
+}
@@ -0,0 +1,152 @@
+mod utils;
+
+#[cfg(test)]
+#[allow(unused_must_use)]
+mod test {
+  use secret_dreamscape::game_state::{Card, Dictionary, WordList};
+  use secret_dreamscape::utils::cards::get_score_for_word;
+  use secret_dreamscape::utils::dictionary::{find_word_id, is_word};
+
+  use crate::utils::*;
+
+  // letters of the english alphabet are indexes from A
+  fn letters(word: &str) -> Vec<u8> {
+    word.bytes().map(|b| b.wrapping_sub(b'A')).collect()
+  }
+
+  /// Test if every word is found with its line in words.txt as id
+  #[test]
+  fn every_word_keeps_its_id() {
+    let words = include_str!("../src/words.txt");
+    for (line, word) in words.split('\n').enumerate() {
+      assert_eq!(
+        find_word_id(&WordList::English, &letters(word)),
+        Some(line),
+        "Wrong id for {}",
+        word
+      );
+    }
+  }
+
+  /// Test if anything that isn't in the dictionary is rejected
+  #[test]
+  fn non_words_are_rejected() {
+    for word in [
+      "",
+      "A",
+      "AAHE",
+      "ZZZZ",
+      "QUIZZINGS",
+      "BILLYCANS",
+      "aah",
+      "AAH ",
+      "ÄA",
+    ]
+    .iter()
+    {
+      assert!(
+        !is_word(&WordList::English, &letters(word)),
+        "{} was accepted",
+        word
+      );
+      assert_eq!(
+        find_word_id(&WordList::English, &letters(word)),
+        None,
+        "{} has an id",
+        word
+      );
+    }
+    assert!(
+      is_word(&WordList::English, &letters("AA")),
+      "First word wasn't found"
+    );
+    assert!(
+      is_word(&WordList::English, &letters("ZZZ")),
+      "Last word wasn't found"
+    );
+    assert!(
+      is_word(&WordList::English, &letters("BILLYCAN")),
+      "Eight letter word wasn't found"
+    );
+  }
+
+  /// Test if words are scored with the points of the table's dictionary
+  #[test]
+  fn custom_points_are_used() {
+    let word: Vec<Card> = letters("BANDIT")
+      .iter()
+      .map(|letter| Card {
+        letter: *letter,
+        gold: false,
+        blank: false,
+      })
+      .collect();
+    assert_eq!(get_score_for_word(&word, &Dictionary::default()), 9);
+
+    let flat = Dictionary {
+      points: vec![1; 26],
+      ..Dictionary::default()
+    };
+    assert_eq!(
+      get_score_for_word(&word, &flat),
+      6,
+      "Custom points weren't used"
+    );
+  }
+
+  /// Test if the deck is built from the dictionary's letter distribution
+  #[test]
+  fn custom_distribution_is_dealt() {
+    let dictionary = Dictionary {
+      distribution: vec![(0, 20), (1, 20)],
+      ..Dictionary::default()
+    };
+    let mut msg = default_init_msg(false);
+    msg.dictionary = Some(dictionary.clone());
+    let (init_result, mut deps) = init_with_n_players_and_msg(2, msg);
+    assert!(init_result.is_ok(), "Custom dictionary was rejected");
+
+    assert!(
+      get_config(&mut deps).dictionary == dictionary,
+      "Custom dictionary wasn't returned"
+    );
+    for i in 0..2 {
+      let game = get_game_state(&mut deps, i);
+      assert_eq!(game.hand.len(), 5, "Hand wasn't dealt");
+      assert!(
+        game.hand.iter().all(|c| c.letter < 2),
+        "Card outside the distribution was dealt"
+      );
+    }
+  }
+
+  /// Test if dictionaries that can't make a deck or score its letters are rejected
+  #[test]
+  fn invalid_dictionary_is_rejected() {
+    let invalid_dictionaries = vec![
+      Dictionary {
+        points: vec![1; 25],
+        ..Dictionary::default()
+      },
+      Dictionary {
+        distribution: vec![(26, 10)],
+        ..Dictionary::default()
+      },
+      Dictionary {
+        distribution: vec![],
+        ..Dictionary::default()
+      },
+      // not enough cards for 4 hands and the river
+      Dictionary {
+        distribution: vec![(0, 24)],
+        ..Dictionary::default()
+      },
+    ];
+    for dictionary in invalid_dictionaries {
+      let mut msg = default_init_msg(false);
+      msg.dictionary = Some(dictionary);
+      let (init_result, _) = init_with_n_players_and_msg(0, msg);
+      assert!(init_result.is_err(), "Invalid dictionary was accepted");
+    }
+  }
+}
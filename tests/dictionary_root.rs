@@ -0,0 +1,205 @@
+mod utils;
+
+#[cfg(test)]
+#[allow(unused_must_use)]
+mod test {
+  use cosmwasm_std::testing::{mock_env, MockApi, MockQuerier, MockStorage};
+  use cosmwasm_std::{Binary, Extern, HandleResult, HumanAddr, Uint128};
+
+  use secret_dreamscape::contract::{handle, HandleMsg};
+  use secret_dreamscape::game_state::{Dictionary, WordProof};
+  use secret_dreamscape::utils::dictionary::{merkle_node, word_leaf};
+
+  use crate::utils::*;
+
+  // billycan, bandit, cat and dog as the 4 leaves of the tree
+  fn leaves() -> Vec<[u8; 32]> {
+    let words: Vec<Vec<u8>> = vec![
+      vec![1, 8, 11, 11, 24, 2, 0, 13],
+      vec![1, 0, 13, 3, 8, 19],
+      vec![2, 0, 19],
+      vec![3, 14, 6],
+    ];
+    words
+      .iter()
+      .enumerate()
+      .map(|(id, letters)| word_leaf(id as u32, letters))
+      .collect()
+  }
+
+  fn root() -> Binary {
+    let leaves = leaves();
+    Binary(
+      merkle_node(
+        &merkle_node(&leaves[0], &leaves[1]),
+        &merkle_node(&leaves[2], &leaves[3]),
+      )
+      .to_vec(),
+    )
+  }
+
+  fn proof(word_id: u32) -> WordProof {
+    let leaves = leaves();
+    let sibling = leaves[(word_id ^ 1) as usize];
+    let other_half = if word_id < 2 {
+      merkle_node(&leaves[2], &leaves[3])
+    } else {
+      merkle_node(&leaves[0], &leaves[1])
+    };
+    WordProof {
+      word_id,
+      siblings: vec![Binary(sibling.to_vec()), Binary(other_half.to_vec())],
+    }
+  }
+
+  fn init_with_root() -> Extern<MockStorage, MockApi, MockQuerier> {
+    let mut msg = default_init_msg(false);
+    msg.admin = Some(HumanAddr("admin".to_string()));
+    msg.dictionary = Some(Dictionary {
+      merkle_root: Some(root()),
+      ..fixture_dictionary()
+    });
+    let (_, mut deps) = init_with_n_players_and_msg(2, msg);
+
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    send_bet(&mut deps, 1, Uint128(1_000_000));
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    send_bet(&mut deps, 1, Uint128(1_000_000));
+    deps
+  }
+
+  fn put_down_proven_word(
+    deps: &mut Extern<MockStorage, MockApi, MockQuerier>,
+    player: usize,
+    word: Vec<u8>,
+    proof: Option<WordProof>,
+  ) -> HandleResult {
+    handle(
+      deps,
+      mock_env(format!("player{}", player), &[]),
+      HandleMsg::PutDownCard {
+        indexes: word,
+        opened_dictionary: false,
+        proof,
+        blank_letters: vec![],
+      },
+    )
+  }
+
+  fn set_root(
+    deps: &mut Extern<MockStorage, MockApi, MockQuerier>,
+    sender: &str,
+    root: Binary,
+  ) -> HandleResult {
+    handle(
+      deps,
+      mock_env(sender, &[]),
+      HandleMsg::SetDictionaryRoot { root },
+    )
+  }
+
+  /// Test if only words proven against the root score on a table with a merkle root
+  #[test]
+  fn proven_words_score() {
+    let mut deps = init_with_root();
+
+    // p0: y, t, g, c, l
+    // p1: r, t, i, a, d
+    // river: l, i, n, a, b
+    let billycan = vec![254, 251, 250, 4, 0, 3, 253, 252];
+    let result = put_down_proven_word(&mut deps, 0, billycan, Some(proof(0)));
+    assert!(result.is_ok(), "Proven word was rejected");
+    // bandit is in the tree too, but without a proof it doesn't count
+    let bandit = vec![254, 253, 252, 4, 2, 1];
+    put_down_proven_word(&mut deps, 1, bandit, None);
+
+    let game = get_game_state(&mut deps, 0);
+    assert_eq!(game.words[0].points, 15, "Proven word didn't score");
+    assert_eq!(game.words[1].points, 0, "Unproven word scored");
+    assert_eq!(
+      game.winner,
+      Some(HumanAddr("player0".to_string())),
+      "Proven word didn't win"
+    );
+  }
+
+  /// Test if a proof for another word or position is rejected
+  #[test]
+  fn invalid_proofs_are_rejected() {
+    let mut deps = init_with_root();
+
+    let billycan = vec![254, 251, 250, 4, 0, 3, 253, 252];
+    let result = put_down_proven_word(&mut deps, 0, billycan.clone(), Some(proof(1)));
+    assert!(result.is_err(), "Proof of another word was accepted");
+
+    let mut wrong_sibling = proof(0);
+    wrong_sibling.siblings[0] = Binary(vec![0; 32]);
+    let result = put_down_proven_word(&mut deps, 0, billycan.clone(), Some(wrong_sibling));
+    assert!(result.is_err(), "Proof with a wrong sibling was accepted");
+
+    let result = put_down_proven_word(&mut deps, 0, billycan, Some(proof(0)));
+    assert!(
+      result.is_ok(),
+      "Word couldn't be put down after a bad proof"
+    );
+  }
+
+  /// Test if only the admin can change the root, and only between hands
+  #[test]
+  fn admin_can_update_root_between_hands() {
+    let mut deps = init_with_root();
+    let new_root = Binary(vec![7; 32]);
+
+    let result = set_root(&mut deps, "admin", new_root.clone());
+    assert!(result.is_err(), "Root was changed mid-hand");
+
+    put_down_proven_word(
+      &mut deps,
+      0,
+      vec![254, 251, 250, 4, 0, 3, 253, 252],
+      Some(proof(0)),
+    );
+    put_down_proven_word(&mut deps, 1, vec![254, 253, 252, 4, 2, 1], Some(proof(1)));
+
+    let result = set_root(&mut deps, "player0", new_root.clone());
+    assert!(result.is_err(), "Player changed the root");
+    let result = set_root(&mut deps, "admin", Binary(vec![7; 31]));
+    assert!(result.is_err(), "Root of the wrong length was accepted");
+    let result = set_root(&mut deps, "admin", new_root.clone());
+    assert!(result.is_ok(), "Admin couldn't change the root");
+
+    let config = get_config(&mut deps);
+    assert_eq!(
+      config.dictionary.merkle_root,
+      Some(new_root),
+      "New root wasn't saved"
+    );
+    assert_eq!(config.admin, Some(HumanAddr("admin".to_string())));
+  }
+
+  /// Test if a table without a root can't be created when the contract has no word lists
+  #[cfg(not(feature = "word-lists"))]
+  #[test]
+  fn root_is_required_without_word_lists() {
+    let (init_result, _) = init_with_n_players_and_msg(0, default_init_msg(false));
+    assert!(
+      init_result.is_err(),
+      "Table without words to check against was created"
+    );
+  }
+
+  /// Test if a table can't be created with a root of the wrong length
+  #[test]
+  fn invalid_root_is_rejected() {
+    let mut msg = default_init_msg(false);
+    msg.dictionary = Some(Dictionary {
+      merkle_root: Some(Binary(vec![1, 2, 3])),
+      ..Dictionary::default()
+    });
+    let (init_result, _) = init_with_n_players_and_msg(0, msg);
+    assert!(
+      init_result.is_err(),
+      "Root of the wrong length was accepted"
+    );
+  }
+}
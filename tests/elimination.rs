@@ -0,0 +1,208 @@
+mod utils;
+
+#[cfg(test)]
+#[allow(unused_must_use)]
+mod test {
+  use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage};
+  use cosmwasm_std::{
+    from_binary, BankMsg, Coin, CosmosMsg, Extern, HandleResult, HumanAddr, Uint128,
+  };
+
+  use secret_dreamscape::game_state::{DamageRule, TableConfig};
+  use secret_dreamscape::query::{query, QueryMsg};
+
+  use crate::utils::*;
+
+  fn play_hand(
+    deps: &mut Extern<MockStorage, MockApi, MockQuerier>,
+    word0: Vec<u8>,
+    word1: Vec<u8>,
+  ) -> HandleResult {
+    // both players bet in the blind and in the flop, whoever the button left to act first
+    for _ in 0..4 {
+      let player = player_on_action(deps).unwrap();
+      send_bet(deps, player, Uint128(1_000_000));
+    }
+
+    put_down_word(deps, 0, word0);
+    put_down_word(deps, 1, word1)
+  }
+
+  /// Test if the player with the lower scoring word loses hp at the showdown
+  #[test]
+  fn showdown_loser_loses_hp() {
+    let (_, mut deps) = init_with_2_players(false);
+
+    // p0: y, t, g, c, l
+    // p1: r, t, i, a, d
+    // river: l, i, n, a, b
+    play_hand(
+      &mut deps,
+      vec![254, 251, 250, 4, 0, 3, 253, 252], // billycan: 15
+      vec![254, 253, 252, 4, 2, 1],           // bandit: 9
+    );
+
+    let game = get_game_state(&mut deps, 0);
+    assert_eq!(game.players[0].hp, 5, "Winner lost hp");
+    assert_eq!(game.players[1].hp, 4, "Loser didn't lose hp");
+  }
+
+  /// Test if the damage grows with the score gap when the table is configured that way
+  #[test]
+  fn damage_scales_with_score_gap() {
+    let mut init_msg = default_init_msg(false);
+    init_msg.table = Some(TableConfig {
+      damage: DamageRule {
+        base: 1,
+        points_per_extra_damage: 3,
+      },
+      ..TableConfig::default()
+    });
+    let (_, mut deps) = init_with_n_players_and_msg(2, init_msg);
+
+    play_hand(
+      &mut deps,
+      vec![254, 251, 250, 4, 0, 3, 253, 252], // billycan: 15
+      vec![254, 253, 252, 4, 2, 1],           // bandit: 9
+    );
+
+    let game = get_game_state(&mut deps, 0);
+    // 1 base damage + 6 points behind / 3
+    assert_eq!(game.players[1].hp, 2, "Damage didn't scale with the gap");
+  }
+
+  /// Test if a table can't be created where losing costs no hp
+  #[test]
+  fn zero_damage_is_rejected() {
+    let mut init_msg = default_init_msg(false);
+    init_msg.table = Some(TableConfig {
+      damage: DamageRule {
+        base: 0,
+        points_per_extra_damage: 0,
+      },
+      ..TableConfig::default()
+    });
+    let (init_result, _) = init_with_n_players_and_msg(0, init_msg);
+
+    assert!(init_result.is_err(), "Table without damage was created");
+  }
+
+  /// Test if a player who left doesn't count as standing when the others are eliminated
+  #[test]
+  fn player_who_left_isnt_left_standing() {
+    let mut msg = default_init_msg(false);
+    msg.table = Some(TableConfig {
+      starting_hp: 1,
+      ..TableConfig::default()
+    });
+    let mut deps = init_with_chips_and_msg(vec![5_000_000, 5_000_000, 5_000_000], msg);
+    leave(&mut deps, 2);
+    for _ in 0..4 {
+      let player = player_on_action(&mut deps).unwrap();
+      send_bet(&mut deps, player, Uint128(1_000_000));
+    }
+
+    // p0: v, d, d, j, u
+    // p1: a, e, a, e, a
+    // river: w, t, y, s, r
+    put_down_word(&mut deps, 0, vec![3, 4, 251]); // jut: 10
+    put_down_word(&mut deps, 1, vec![]);
+
+    let game = get_game_state(&mut deps, 0);
+    assert_eq!(game.players[1].hp, 0, "Loser wasn't eliminated");
+    let result = query(&deps, QueryMsg::GetResult {});
+    assert!(result.is_ok(), "Player who left kept the game going");
+    let winners: Vec<HumanAddr> = from_binary(&result.unwrap()).unwrap();
+    assert_eq!(winners, vec![HumanAddr("player0".to_string())]);
+  }
+
+  /// Test if the last player standing wins the game and every player's chips are paid out
+  #[test]
+  fn last_player_standing_wins_and_is_paid_out() {
+    let (_, mut deps) = init_with_2_players(false);
+
+    play_hand(
+      &mut deps,
+      vec![254, 251, 250, 4, 0, 3, 253, 252], // billycan: 15
+      vec![254, 253, 252, 4, 2, 1],           // bandit: 9
+    );
+    request_next_turn(&mut deps, 0);
+    play_hand(
+      &mut deps,
+      vec![0, 4, 254, 252, 253, 251],    // troika: 10
+      vec![250, 251, 2, 1, 0, 252, 253], // tamarik: 0
+    );
+    request_next_turn(&mut deps, 1);
+    play_hand(
+      &mut deps,
+      vec![0, 4, 1, 254, 251, 250, 3, 252], // grapiest: 11
+      vec![1, 2, 3, 254, 251, 250, 0, 252], // weepiest: 13
+    );
+    request_next_turn(&mut deps, 1);
+    play_hand(
+      &mut deps,
+      vec![0, 3, 253, 1, 252, 254, 251, 4], // aduncate: 11
+      vec![252, 4, 254, 0, 3],              // craws: 10
+    );
+    request_next_turn(&mut deps, 0);
+    play_hand(
+      &mut deps,
+      vec![3, 250, 4, 2, 0, 251, 1], // fishnet: 26
+      vec![2, 3],                    // ox: 18
+    );
+
+    let game = get_game_state(&mut deps, 0);
+    assert!(game.winner.is_some(), "Winner is not set for the turn");
+    assert_eq!(game.players[1].hp, 1, "Loser has the wrong hp");
+
+    request_next_turn(&mut deps, 0);
+    let final_step = play_hand(
+      &mut deps,
+      vec![3, 1, 252, 250, 2, 254, 253], // flogged: 26
+      vec![2, 0, 253],                   // zed: 22
+    )
+    .unwrap();
+
+    let game = get_game_state(&mut deps, 0);
+    assert_eq!(game.players[1].hp, 0, "Loser wasn't eliminated");
+    assert_eq!(
+      game.winner,
+      Some(HumanAddr("player0".to_string())),
+      "Last player standing didn't win"
+    );
+
+    let payouts: Vec<(HumanAddr, Coin)> = final_step
+      .messages
+      .into_iter()
+      .filter_map(|msg| match msg {
+        CosmosMsg::Bank(BankMsg::Send {
+          to_address, amount, ..
+        }) => Some((to_address, amount[0].clone())),
+        _ => None,
+      })
+      .collect();
+    assert_eq!(
+      payouts,
+      vec![
+        (
+          HumanAddr("player1".to_string()),
+          Coin {
+            amount: Uint128(1_600_000),
+            denom: "uscrt".to_string(),
+          }
+        ),
+        (
+          HumanAddr("player0".to_string()),
+          Coin {
+            amount: Uint128(16_000_000),
+            denom: "uscrt".to_string(),
+          }
+        ),
+      ],
+      "Chips weren't paid out at the end of the game"
+    );
+
+    let next_turn = request_next_turn(&mut deps, 0);
+    assert!(next_turn.is_err(), "Game continued after it was won");
+  }
+}
@@ -0,0 +1,88 @@
+mod utils;
+
+#[cfg(test)]
+#[allow(unused_must_use)]
+mod test {
+  use cosmwasm_std::{from_binary, HandleResponse, HumanAddr, Uint128};
+
+  use secret_dreamscape::contract::HandleAnswer;
+  use secret_dreamscape::game_state::GameRound;
+
+  use crate::utils::*;
+
+  fn log_value(response: &HandleResponse, key: &str) -> Option<String> {
+    response
+      .log
+      .iter()
+      .find(|attr| attr.key == key)
+      .map(|attr| attr.value.clone())
+  }
+
+  /// Test if an action is logged with who made it and the amount it moved
+  #[test]
+  fn action_is_logged_with_player_and_amount() {
+    let (_, mut deps) = init_with_2_players(false);
+    let response = send_bet(&mut deps, 0, Uint128(1_000_000)).unwrap();
+    assert_eq!(log_value(&response, "action"), Some("bet".to_string()));
+    assert_eq!(log_value(&response, "player"), Some("player0".to_string()));
+    assert_eq!(log_value(&response, "amount"), Some("1000000".to_string()));
+    assert_eq!(log_value(&response, "round"), None, "Round didn't change");
+
+    let answer: HandleAnswer = from_binary(&response.data.unwrap()).unwrap();
+    assert!(
+      answer
+        == HandleAnswer::GameAction {
+          round: GameRound::Blind,
+          turn: 0,
+          pool: 1_000_000,
+          chips: Some(9_000_000),
+          action_on: Some(HumanAddr("player1".to_string())),
+        },
+      "Wrong data returned"
+    );
+  }
+
+  /// Test if moving to another round is logged
+  #[test]
+  fn round_transition_is_logged() {
+    let (_, mut deps) = init_with_2_players(false);
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    let response = send_bet(&mut deps, 1, Uint128(1_000_000)).unwrap();
+    assert_eq!(log_value(&response, "round"), Some("flop".to_string()));
+  }
+
+  /// Test if settling a hand logs the winner, the payouts and the rake
+  #[test]
+  fn settled_hand_is_logged() {
+    let (_, mut deps) = init_with_2_players(false);
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    send_bet(&mut deps, 1, Uint128(1_000_000));
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    let response = fold(&mut deps, 1).unwrap();
+    assert_eq!(log_value(&response, "action"), Some("fold".to_string()));
+    assert_eq!(log_value(&response, "winner"), Some("player0".to_string()));
+    // the uncalled bet comes back and only the called pot is raked
+    assert_eq!(
+      log_value(&response, "payout"),
+      Some("player0:2800000".to_string())
+    );
+    assert_eq!(log_value(&response, "rake"), Some("200000".to_string()));
+
+    let response = request_next_turn(&mut deps, 0).unwrap();
+    assert_eq!(log_value(&response, "turn"), Some("1".to_string()));
+    assert_eq!(log_value(&response, "round"), Some("blind".to_string()));
+    assert_eq!(log_value(&response, "winner"), None);
+  }
+
+  /// Test if viewing key actions are logged without a game payload
+  #[test]
+  fn key_actions_are_logged_without_game_data() {
+    let (_, mut deps) = init_with_2_players(false);
+    let response = set_viewing_key(&mut deps, 0, "another key").unwrap();
+    assert_eq!(
+      log_value(&response, "action"),
+      Some("set_viewing_key".to_string())
+    );
+    assert!(response.data.is_none(), "Game data was returned");
+  }
+}
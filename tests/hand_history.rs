@@ -0,0 +1,124 @@
+mod utils;
+
+#[cfg(test)]
+#[allow(unused_must_use)]
+mod test {
+  use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage};
+  use cosmwasm_std::{Extern, HumanAddr, Uint128};
+
+  use secret_dreamscape::game_state::{GameRound, HandOutcome, PlayerAction};
+
+  use crate::utils::*;
+
+  fn play_to_showdown(deps: &mut Extern<MockStorage, MockApi, MockQuerier>) {
+    send_bet(deps, 0, Uint128(1_000_000));
+    send_bet(deps, 1, Uint128(1_000_000));
+    send_bet(deps, 0, Uint128(1_000_000));
+    send_bet(deps, 1, Uint128(1_000_000));
+
+    // p0: y, t, g, c, l
+    // p1: r, t, i, a, d
+    // river: l, i, n, a, b
+    put_down_word(deps, 0, vec![254, 251, 250, 4, 0, 3, 253, 252]); // billycan: 15
+    put_down_word(deps, 1, vec![254, 253, 252, 4, 2, 1]); // bandit: 9
+  }
+
+  /// Test if a settled hand is archived with its actions, words and payouts
+  #[test]
+  fn settled_hand_is_archived() {
+    let (_, mut deps) = init_with_2_players(false);
+    let history = get_hand_history(&mut deps, 0, 10);
+    assert_eq!(history.total, 0, "Hand in play was archived");
+
+    play_to_showdown(&mut deps);
+    let history = get_hand_history(&mut deps, 0, 10);
+    assert_eq!(history.total, 1, "Hand wasn't archived");
+    let hand = &history.hands[0];
+    assert_eq!(hand.turn, 0);
+    assert_eq!(hand.river.len(), 5, "River wasn't archived");
+    assert!(
+      hand.outcome == Some(HandOutcome::Won(HumanAddr("player0".to_string()))),
+      "Winner wasn't archived"
+    );
+    assert_eq!(hand.actions.len(), 6, "Actions weren't logged");
+    assert!(hand.actions[0].addr == HumanAddr("player0".to_string()));
+    assert!(hand.actions[0].round == GameRound::Blind);
+    assert!(hand.actions[0].action == PlayerAction::SentBet(1_000_000));
+    assert!(hand.actions[5].action == PlayerAction::ChoseWord);
+    let points: Vec<u16> = hand.words.iter().map(|w| w.points).collect();
+    assert_eq!(points, vec![15, 9], "Words weren't archived");
+    assert_eq!(hand.payouts.len(), 1);
+    assert_eq!(hand.payouts[0].amount, 3_600_000, "Payout wasn't archived");
+    assert_eq!(hand.rake, 400_000, "Rake wasn't archived");
+    assert!(hand.hands.is_empty(), "Dealt hands were made public");
+  }
+
+  /// Test if players only see their own dealt hand in the history
+  #[test]
+  fn player_sees_only_their_own_hand() {
+    let (_, mut deps) = init_with_2_players(false);
+    play_to_showdown(&mut deps);
+
+    let history = get_player_hand_history(&mut deps, 1, 0, 10);
+    let hands = &history.hands[0].hands;
+    assert_eq!(hands.len(), 1, "Other players' hands were shown");
+    assert!(hands[0].addr == HumanAddr("player1".to_string()));
+    assert_eq!(hands[0].cards.len(), 5, "Own hand wasn't shown");
+  }
+
+  /// Test if the history outlives the next hand and can be paged through
+  #[test]
+  fn history_is_kept_and_paginated() {
+    let (_, mut deps) = init_with_2_players(false);
+    fold(&mut deps, 0);
+    request_next_turn(&mut deps, 0);
+    let game = get_game_state(&mut deps, 0);
+    assert!(game.words.is_empty(), "Previous hand wasn't cleared");
+    fold(&mut deps, 1);
+
+    let history = get_hand_history(&mut deps, 0, 10);
+    assert_eq!(history.total, 2, "Both hands weren't archived");
+    let page = get_hand_history(&mut deps, 1, 10);
+    assert_eq!(page.hands.len(), 1);
+    assert_eq!(page.hands[0].turn, 1, "Page didn't start at the right turn");
+    let page = get_hand_history(&mut deps, 0, 1);
+    assert_eq!(page.hands.len(), 1, "Limit wasn't applied");
+    assert_eq!(page.hands[0].turn, 0);
+    assert!(page.hands[0].actions[0].action == PlayerAction::Folded);
+  }
+
+  /// Test if the river stays hidden in the history of hands that ended before it was shown
+  #[test]
+  fn unshown_river_isnt_archived() {
+    let (_, mut deps) = init_with_2_players(false);
+    fold(&mut deps, 0);
+
+    let history = get_hand_history(&mut deps, 0, 10);
+    assert!(
+      history.hands[0].river.is_empty(),
+      "Unshown river was made public"
+    );
+    let history = get_player_hand_history(&mut deps, 1, 0, 10);
+    assert!(
+      history.hands[0].river.is_empty(),
+      "Unshown river was shown to a player"
+    );
+  }
+
+  /// Test if hands past the 256th are archived without overwriting the first ones
+  #[test]
+  fn history_outgrows_a_byte() {
+    let (_, mut deps) = init_with_2_players(false);
+    for _ in 0..300 {
+      let player = player_on_action(&mut deps).unwrap();
+      fold(&mut deps, player).unwrap();
+      request_next_turn(&mut deps, 0).unwrap();
+    }
+
+    let history = get_hand_history(&mut deps, 0, 1);
+    assert_eq!(history.total, 300, "Hands weren't all archived");
+    assert_eq!(history.hands[0].turn, 0, "First hand was overwritten");
+    let page = get_hand_history(&mut deps, 299, 1);
+    assert_eq!(page.hands[0].turn, 299);
+  }
+}
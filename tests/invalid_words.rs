@@ -0,0 +1,105 @@
+mod utils;
+
+#[cfg(test)]
+#[allow(unused_must_use)]
+mod test {
+  use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage};
+  use cosmwasm_std::{Extern, HumanAddr, Uint128};
+
+  use secret_dreamscape::contract::InitMsg;
+  use secret_dreamscape::game_state::{Dictionary, InvalidWordPolicy, TableConfig};
+
+  use crate::utils::*;
+
+  // both players bet through to the round words are put down in
+  fn init_at_choice(msg: InitMsg) -> Extern<MockStorage, MockApi, MockQuerier> {
+    let (_, mut deps) = init_with_n_players_and_msg(2, msg);
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    send_bet(&mut deps, 1, Uint128(1_000_000));
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    send_bet(&mut deps, 1, Uint128(1_000_000));
+    deps
+  }
+
+  fn rejecting_table() -> InitMsg {
+    let mut msg = default_init_msg(false);
+    msg.table = Some(TableConfig {
+      invalid_words: InvalidWordPolicy::Reject,
+      ..TableConfig::default()
+    });
+    msg
+  }
+
+  /// Test if empty words and words that aren't in the dictionary are rejected
+  #[test]
+  fn invalid_words_are_rejected() {
+    let mut deps = init_at_choice(rejecting_table());
+
+    // p0: y, t, g, c, l
+    // p1: r, t, i, a, d
+    // river: l, i, n, a, b
+    let result = put_down_word(&mut deps, 0, vec![]);
+    assert!(result.is_err(), "Empty word was accepted");
+    let result = put_down_word(&mut deps, 0, vec![0, 1]); // yt
+    assert!(result.is_err(), "Word outside the dictionary was accepted");
+
+    let result = put_down_word(&mut deps, 0, vec![254, 251, 250, 4, 0, 3, 253, 252]); // billycan
+    assert!(result.is_ok(), "Word in the dictionary was rejected");
+  }
+
+  /// Test if invalid words lose to words in the dictionary, even those worth no points
+  #[test]
+  fn invalid_words_lose_automatically() {
+    let mut msg = default_init_msg(false);
+    msg.dictionary = Some(Dictionary {
+      points: vec![0; 26],
+      ..fixture_dictionary()
+    });
+    let mut deps = init_at_choice(msg);
+
+    let result = put_down_word(&mut deps, 0, vec![0, 1]); // yt
+    assert!(result.is_ok(), "Word outside the dictionary was rejected");
+    put_down_word(&mut deps, 1, vec![254, 253, 252, 4, 2, 1]); // bandit
+
+    let game = get_game_state(&mut deps, 0);
+    assert_eq!(game.words[1].points, 0, "Word wasn't worth 0 points");
+    assert_eq!(
+      game.winner,
+      Some(HumanAddr("player1".to_string())),
+      "Invalid word didn't lose"
+    );
+  }
+
+  /// Test if an empty word can be put down, and loses, when invalid words aren't rejected
+  #[test]
+  fn empty_word_loses() {
+    let mut deps = init_at_choice(default_init_msg(false));
+
+    let result = put_down_word(&mut deps, 0, vec![]);
+    assert!(result.is_ok(), "Empty word was rejected");
+    put_down_word(&mut deps, 1, vec![254, 253, 252, 4, 2, 1]); // bandit
+
+    let game = get_game_state(&mut deps, 0);
+    assert_eq!(
+      game.winner,
+      Some(HumanAddr("player1".to_string())),
+      "Empty word didn't lose"
+    );
+  }
+
+  /// Test if the config query reports the table's invalid word policy
+  #[test]
+  fn policy_is_reported() {
+    let (_, mut deps) = init_with_n_players(0, false);
+    assert!(
+      get_config(&mut deps).table.invalid_words == InvalidWordPolicy::AutoLoss,
+      "Invalid words didn't default to an automatic loss"
+    );
+
+    let (_, mut deps) = init_with_n_players_and_msg(0, rejecting_table());
+    assert!(
+      get_config(&mut deps).table.invalid_words == InvalidWordPolicy::Reject,
+      "Rejecting policy wasn't reported"
+    );
+  }
+}
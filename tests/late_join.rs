@@ -0,0 +1,63 @@
+mod utils;
+
+#[cfg(test)]
+#[allow(unused_must_use)]
+mod test {
+  use cosmwasm_std::{BankMsg, CosmosMsg, HumanAddr, Uint128};
+
+  use secret_dreamscape::game_state::GameRound;
+
+  use crate::utils::*;
+
+  /// Test if a player who joins mid-hand waits for the next one instead of being folded into it
+  #[test]
+  fn late_joiner_waits_for_the_next_hand() {
+    let (_, mut deps) = init_with_2_players(false);
+    join(&mut deps, 2, 2, false);
+    buy_chips(&mut deps, 2, Uint128(5_000_000));
+    set_viewing_key(&mut deps, 2, "key2");
+
+    let game = get_game_state(&mut deps, 2);
+    assert!(game.players[2].waiting, "Late joiner isn't waiting");
+    assert!(!game.players[2].folded, "Late joiner was folded");
+    assert!(game.hand.is_empty(), "Late joiner was dealt in mid-hand");
+    assert_eq!(game.join_queue, vec![HumanAddr("player2".to_string())]);
+
+    let bet = send_bet(&mut deps, 2, Uint128(1_000_000));
+    assert!(bet.is_err(), "Waiting player could bet");
+
+    // the hand is played out without the waiting player
+    fold(&mut deps, 0);
+    let game = get_game_state(&mut deps, 2);
+    assert!(game.action_on.is_none(), "Hand waited for the late joiner");
+
+    request_next_turn(&mut deps, 0).unwrap();
+    let game = get_game_state(&mut deps, 2);
+    assert!(game.round == GameRound::Blind, "Next hand didn't start");
+    assert!(!game.players[2].waiting, "Late joiner wasn't dealt in");
+    assert!(!game.players[2].folded, "Late joiner sat out the next hand");
+    assert_eq!(game.hand.len(), 5, "Late joiner wasn't dealt a hand");
+    assert!(game.join_queue.is_empty(), "Queue wasn't cleared");
+  }
+
+  /// Test if a waiting player can leave mid-hand and is paid out right away
+  #[test]
+  fn waiting_player_can_leave_mid_hand() {
+    let (_, mut deps) = init_with_2_players(false);
+    join(&mut deps, 2, 2, false);
+    buy_chips(&mut deps, 2, Uint128(5_000_000));
+
+    let leave_result = leave(&mut deps, 2).unwrap();
+    assert!(
+      leave_result
+        .messages
+        .iter()
+        .any(|msg| matches!(msg, CosmosMsg::Bank(BankMsg::Send { .. }))),
+      "Waiting player's chips weren't paid out"
+    );
+    let game = get_game_state(&mut deps, 0);
+    assert_eq!(game.players.len(), 2, "Seat wasn't released");
+    assert!(game.join_queue.is_empty(), "Player stayed in the queue");
+    assert!(game.round == GameRound::Blind, "Hand was interrupted");
+  }
+}
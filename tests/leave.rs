@@ -0,0 +1,154 @@
+mod utils;
+
+#[cfg(test)]
+#[allow(unused_must_use)]
+mod test {
+  use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage};
+  use cosmwasm_std::{BankMsg, CosmosMsg, Extern, Uint128};
+
+  use secret_dreamscape::game_state::GameRound;
+
+  use crate::utils::*;
+
+  fn sends_chips(messages: &[CosmosMsg]) -> bool {
+    messages
+      .iter()
+      .any(|msg| matches!(msg, CosmosMsg::Bank(BankMsg::Send { .. })))
+  }
+
+  // player2 leaves in the given round, then whoever is left to act folds to end the hand
+  fn leave_mid_hand(deps: &mut Extern<MockStorage, MockApi, MockQuerier>, round: GameRound) {
+    let game = get_game_state(deps, 0);
+    assert!(game.round == round, "Hand isn't in the expected round");
+    let pool = game.pool;
+
+    let leave_result = leave(deps, 2).unwrap();
+    assert!(
+      !sends_chips(&leave_result.messages),
+      "Chips were paid out mid-hand"
+    );
+    let game = get_game_state(deps, 0);
+    assert_eq!(game.players.len(), 3, "Seat was released mid-hand");
+    assert!(game.players[2].folded, "Player who left wasn't folded");
+    assert!(game.players[2].left, "Player wasn't marked as left");
+    assert_eq!(game.pool, pool, "Bets of the player who left were lost");
+
+    let again = leave(deps, 2);
+    assert!(again.is_err(), "Player could leave twice");
+    let bet = send_bet(deps, 2, Uint128(1_000_000));
+    assert!(bet.is_err(), "Player who left could still bet");
+
+    let folder = player_on_action(deps).unwrap_or(1);
+    let fold_result = fold(deps, folder).unwrap();
+    assert!(
+      sends_chips(&fold_result.messages),
+      "Chips of the player who left weren't settled"
+    );
+    let game = get_game_state(deps, 0);
+    assert!(game.winner.is_some(), "Hand wasn't settled");
+    assert_eq!(
+      game.players.len(),
+      3,
+      "Seat was released before the next hand"
+    );
+
+    request_next_turn(deps, 0);
+    let game = get_game_state(deps, 0);
+    assert_eq!(game.players.len(), 2, "Seat wasn't released");
+    assert!(game.round == GameRound::Blind, "Next hand didn't start");
+  }
+
+  /// Test if a player who leaves before revealing is removed and the others are dealt in
+  #[test]
+  fn leave_during_reveal() {
+    let (_, mut deps) = init_with_n_players(0, false);
+    for i in 0..3 {
+      join(&mut deps, i, i as u64, false);
+      buy_chips(&mut deps, i, Uint128(10_000_000));
+      set_viewing_key(&mut deps, i, &format!("key{}", i));
+    }
+    reveal(&mut deps, 0, 0);
+    reveal(&mut deps, 1, 1);
+
+    leave(&mut deps, 2);
+    let game = get_game_state(&mut deps, 0);
+    assert_eq!(game.players.len(), 2, "Player wasn't removed");
+    assert!(game.round == GameRound::Blind, "Cards weren't dealt");
+  }
+
+  /// Test if leaving in the blind round folds the player until the hand is over
+  #[test]
+  fn leave_during_blind() {
+    let mut deps = init_with_chips(vec![5_000_000, 5_000_000, 5_000_000]);
+    leave_mid_hand(&mut deps, GameRound::Blind);
+  }
+
+  /// Test if leaving in the matching round folds the player until the hand is over
+  #[test]
+  fn leave_during_matching() {
+    let mut deps = init_with_chips(vec![5_000_000, 5_000_000, 5_000_000]);
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    send_bet(&mut deps, 1, Uint128(2_000_000));
+    send_bet(&mut deps, 2, Uint128(1_000_000));
+    leave_mid_hand(&mut deps, GameRound::Matching);
+  }
+
+  /// Test if leaving in the flop round folds the player until the hand is over
+  #[test]
+  fn leave_during_flop() {
+    let mut deps = init_with_chips(vec![5_000_000, 5_000_000, 5_000_000]);
+    for i in 0..3 {
+      send_bet(&mut deps, i, Uint128(1_000_000));
+    }
+    leave_mid_hand(&mut deps, GameRound::Flop);
+  }
+
+  /// Test if leaving in the second matching round folds the player until the hand is over
+  #[test]
+  fn leave_during_matching2() {
+    let mut deps = init_with_chips(vec![5_000_000, 5_000_000, 5_000_000]);
+    for i in 0..3 {
+      send_bet(&mut deps, i, Uint128(1_000_000));
+    }
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    send_bet(&mut deps, 1, Uint128(2_000_000));
+    send_bet(&mut deps, 2, Uint128(1_000_000));
+    leave_mid_hand(&mut deps, GameRound::Matching2);
+  }
+
+  /// Test if leaving in the choice round folds the player until the hand is over
+  #[test]
+  fn leave_during_choice() {
+    let mut deps = init_with_chips(vec![5_000_000, 5_000_000, 5_000_000]);
+    for _ in 0..2 {
+      for i in 0..3 {
+        send_bet(&mut deps, i, Uint128(1_000_000));
+      }
+    }
+    leave_mid_hand(&mut deps, GameRound::Choice);
+  }
+
+  /// Test if leaving once the hand is over releases the seat and pays out right away
+  #[test]
+  fn leave_between_hands() {
+    let mut deps = init_with_chips(vec![5_000_000, 5_000_000, 5_000_000]);
+    fold(&mut deps, 0);
+    fold(&mut deps, 1);
+
+    let leave_result = leave(&mut deps, 2).unwrap();
+    assert!(
+      sends_chips(&leave_result.messages),
+      "Chips weren't paid out"
+    );
+    let game = get_game_state(&mut deps, 0);
+    assert_eq!(game.players.len(), 2, "Seat wasn't released");
+  }
+
+  /// Test if someone who isn't seated can't leave
+  #[test]
+  fn non_member_cant_leave() {
+    let (_, mut deps) = init_with_2_players(false);
+    let leave_result = leave(&mut deps, 5);
+    assert!(leave_result.is_err(), "Non-member could leave");
+  }
+}
@@ -0,0 +1,188 @@
+mod utils;
+
+#[cfg(test)]
+#[allow(unused_must_use)]
+mod test {
+  use cosmwasm_std::testing::{mock_dependencies, MockApi, MockQuerier, MockStorage};
+  use cosmwasm_std::{Binary, Extern, HumanAddr, ReadonlyStorage, Storage, Uint128};
+  use cosmwasm_storage::PrefixedStorage;
+  use serde::Deserialize;
+
+  use secret_dreamscape::game_state::{GameRound, PlayerAction};
+
+  use crate::utils::*;
+
+  // block time used by mock_env
+  const NOW: u64 = 1_571_797_419;
+
+  fn deps_with_legacy_state() -> Extern<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies(20, &[]);
+    deps
+      .storage
+      .set(b"state", include_bytes!("fixtures/legacy_state.json"));
+    deps
+  }
+
+  // records written by the first versioned layout, 3 players in the matching round of the
+  // second hand after player0 matched player1's raise
+  #[derive(Deserialize)]
+  struct V1Store {
+    version: Binary,
+    config: Binary,
+    board: Binary,
+    deck: Binary,
+    seats: Binary,
+    player0: Binary,
+    player1: Binary,
+    player2: Binary,
+  }
+
+  fn deps_with_v1_store() -> Extern<MockStorage, MockApi, MockQuerier> {
+    let store: V1Store =
+      serde_json_wasm::from_slice(include_bytes!("fixtures/v1_store.json")).unwrap();
+    let mut deps = mock_dependencies(20, &[]);
+    deps.storage.set(b"version", store.version.as_slice());
+    deps.storage.set(b"config", store.config.as_slice());
+    deps.storage.set(b"board", store.board.as_slice());
+    deps.storage.set(b"deck", store.deck.as_slice());
+    deps.storage.set(b"seats", store.seats.as_slice());
+    let mut players = PrefixedStorage::new(b"players", &mut deps.storage);
+    players.set(b"player0", store.player0.as_slice());
+    players.set(b"player1", store.player1.as_slice());
+    players.set(b"player2", store.player2.as_slice());
+    deps
+  }
+
+  /// Test if a table stored as a single json blob is upgraded to the versioned layout on the first action
+  #[test]
+  fn legacy_state_is_upgraded_on_first_action() {
+    let mut deps = deps_with_legacy_state();
+
+    let upgrade = set_viewing_key(&mut deps, 0, "key0");
+    assert!(upgrade.is_ok(), "Legacy table couldn't be upgraded");
+    assert!(
+      deps.storage.get(b"state").is_none(),
+      "Legacy state was left behind"
+    );
+
+    let game = get_game_state(&mut deps, 0);
+    assert!(game.round == GameRound::Flop, "Round was lost");
+    assert_eq!(game.turn, 2, "Turn was lost");
+    assert_eq!(game.pool, 2_000_000, "Pool was lost");
+    assert_eq!(game.level_design, 3, "Level design was lost");
+    assert_eq!(game.players.len(), 2, "Players were lost");
+    assert_eq!(game.players[0].hp, 4, "Hp was lost");
+    assert_eq!(game.players[0].chips, 8_000_000, "Chips were lost");
+    assert_eq!(game.players[1].chips, 9_000_000, "Chips were lost");
+    let letters: Vec<u8> = game.hand.iter().map(|c| c.letter).collect();
+    assert_eq!(letters, vec![24, 19, 6, 2, 11], "Hand was lost");
+    assert!(game.hand[2].gold, "Gold card was lost");
+    assert_eq!(game.river.unwrap().len(), 5, "River was lost");
+    assert_eq!(
+      game.round_deadline,
+      Some(NOW + 120),
+      "Upgraded hand has no deadline"
+    );
+  }
+
+  /// Test if an upgraded table can keep playing the hand it was in
+  #[test]
+  fn upgraded_table_keeps_playing() {
+    let mut deps = deps_with_legacy_state();
+
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    send_bet(&mut deps, 1, Uint128(1_000_000));
+    set_viewing_key(&mut deps, 0, "key0");
+
+    let game = get_game_state(&mut deps, 0);
+    assert!(game.round == GameRound::Choice, "Game round is not choice");
+    assert_eq!(game.pool, 4_000_000, "Bets weren't added to the pool");
+  }
+
+  /// Test if the hand an upgraded table was in is archived under its own turn
+  #[test]
+  fn upgraded_hand_is_archived() {
+    let mut deps = deps_with_legacy_state();
+    set_viewing_key(&mut deps, 0, "key0");
+
+    fold(&mut deps, 0);
+
+    let history = get_hand_history(&mut deps, 0, 10);
+    assert_eq!(history.total, 1, "Upgraded hand wasn't archived");
+    assert_eq!(history.hands[0].turn, 2, "Upgraded hand lost its turn");
+    assert_eq!(
+      history.hands[0].river.len(),
+      5,
+      "Shown river wasn't archived"
+    );
+  }
+
+  /// Test if a table stored in the first versioned layout is upgraded on the first action
+  #[test]
+  fn v1_store_is_upgraded_on_first_action() {
+    let mut deps = deps_with_v1_store();
+
+    let upgrade = set_viewing_key(&mut deps, 0, "key0");
+    assert!(upgrade.is_ok(), "Version 1 table couldn't be upgraded");
+    assert_eq!(
+      deps.storage.get(b"version"),
+      Some(vec![2]),
+      "Version wasn't bumped"
+    );
+
+    let game = get_game_state(&mut deps, 0);
+    assert!(game.round == GameRound::Matching, "Round was lost");
+    assert_eq!(game.turn, 1, "Turn was lost");
+    assert_eq!(game.pool, 5_000_000, "Pool was lost");
+    assert_eq!(game.players.len(), 3, "Players were lost");
+    assert_eq!(game.players[0].hp, 5, "Hp was lost");
+    assert_eq!(game.players[0].chips, 8_000_000, "Chips were lost");
+    assert_eq!(game.players[2].chips, 9_000_000, "Chips were lost");
+    assert!(
+      game.players[0].last_action == Some(PlayerAction::MatchedBet),
+      "Action was read as another one"
+    );
+    assert_eq!(game.players[2].seat, 2, "Seat wasn't given");
+    let letters: Vec<u8> = game.hand.iter().map(|c| c.letter).collect();
+    assert_eq!(letters, vec![0, 0, 17, 12, 18], "Hand was lost");
+    assert!(game.hand[3].gold, "Gold card was lost");
+    assert!(game.hand.iter().all(|c| !c.blank), "Card became a blank");
+    assert_eq!(game.round_deadline, Some(NOW + 120), "Deadline was lost");
+
+    let config = get_config(&mut deps);
+    assert_eq!(config.table.turn_timeout, 120, "Turn timeout was lost");
+    assert_eq!(config.table.rake_percentage, 10, "Rake was lost");
+    assert_eq!(config.dictionary.blanks, 0, "Blanks were added to the deck");
+  }
+
+  /// Test if an upgraded version 1 table finishes its hand and archives it
+  #[test]
+  fn upgraded_v1_table_keeps_playing() {
+    let mut deps = deps_with_v1_store();
+    set_viewing_key(&mut deps, 0, "key0");
+
+    let matched = match_bet(&mut deps, 2, Uint128(1_000_000));
+    assert!(matched.is_ok(), "Upgraded hand couldn't be played on");
+    let game = get_game_state(&mut deps, 0);
+    assert!(game.round == GameRound::Flop, "Round didn't move on");
+    assert_eq!(game.pool, 6_000_000, "Match wasn't added to the pool");
+
+    fold(&mut deps, 0);
+    fold(&mut deps, 1);
+    let history = get_hand_history(&mut deps, 0, 10);
+    assert_eq!(history.total, 1, "Upgraded hand wasn't archived");
+    assert_eq!(history.hands[0].turn, 1, "Upgraded hand lost its turn");
+    let game = get_game_state(&mut deps, 0);
+    assert_eq!(game.winner, Some(HumanAddr("player2".to_string())));
+  }
+
+  /// Test if a table stored by a newer version isn't touched
+  #[test]
+  fn newer_schema_is_rejected() {
+    let mut deps = deps_with_legacy_state();
+    deps.storage.set(b"version", &[3]);
+
+    let action = set_viewing_key(&mut deps, 0, "key0");
+    assert!(action.is_err(), "Table from a newer version was loaded");
+  }
+}
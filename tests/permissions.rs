@@ -1,9 +1,10 @@
 mod utils;
 
 #[cfg(test)]
+#[allow(unused_must_use)]
 mod test {
   use cosmwasm_std::testing::*;
-  use cosmwasm_std::{Coin, Uint128};
+  use cosmwasm_std::Uint128;
 
   use secret_dreamscape::contract::{handle, HandleMsg};
 
@@ -23,12 +24,12 @@ mod test {
     send_bet(&mut deps, 2, Uint128(1_000_000));
     send_bet(&mut deps, 3, Uint128(1_000_000));
 
-    // letters on player1's hands are: y, t, g, c, l
-    // letters on player2's hands are: r, t, i, a, d
-    // letters on player3's hands are: t, a, i, k, o
-    // letters on player4's hands are: a, a, r, m gold, s
-    // letters on river are: l, i, n, a, b
-    put_down_word(&mut deps, 0, vec![254, 251, 250, 4, 0, 3, 253, 252]); // billycan: 15
+    // p0: o, l, p, o, r
+    // p1: e gold, r, a, n gold, r
+    // p2: n, t, i, a, f
+    // p3: i, p, a, a, d
+    // river: e, o, e, r, m
+    put_down_word(&mut deps, 0, vec![250, 254, 2, 252, 4, 0, 253]); // emperor: 11
     let game_state_player0 = get_game_state(&mut deps, 0);
     let game_state_player1 = get_game_state(&mut deps, 1);
     get_game_state(&mut deps, 2);
@@ -42,14 +43,12 @@ mod test {
       "Player word wasn't visible to player"
     );
     let word_obj = word_according_to_player0.word.unwrap();
-    assert_eq!(word_obj.cards[0].letter, 1, "Player word wasn't correct");
-    assert_eq!(word_obj.cards[1].letter, 8, "Player word wasn't correct");
-    assert_eq!(word_obj.cards[2].letter, 11, "Player word wasn't correct");
-    assert_eq!(word_obj.cards[3].letter, 11, "Player word wasn't correct");
-    assert_eq!(word_obj.cards[4].letter, 24, "Player word wasn't correct");
-    assert_eq!(word_obj.cards[5].letter, 2, "Player word wasn't correct");
-    assert_eq!(word_obj.cards[6].letter, 0, "Player word wasn't correct");
-    assert_eq!(word_obj.cards[7].letter, 13, "Player word wasn't correct");
+    let letters: Vec<u8> = word_obj.cards.iter().map(|c| c.letter).collect();
+    assert_eq!(
+      letters,
+      vec![4, 12, 15, 4, 17, 14, 17],
+      "Player word wasn't correct"
+    );
     assert!(
       !word_according_to_player1.visible,
       "Player word was visible to opponent"
@@ -59,9 +58,9 @@ mod test {
       "Player word was visible to opponent"
     );
 
-    put_down_word(&mut deps, 1, vec![254, 253, 252, 4, 2, 1]); // bandit: 9
-    put_down_word(&mut deps, 2, vec![2, 252, 3, 254, 250, 4, 0]); // inkblot: 13
-    put_down_word(&mut deps, 3, vec![254, 253, 251, 250, 4, 3, 0, 252]); // bailsman: 24
+    put_down_word(&mut deps, 1, vec![1, 0, 3, 2, 254, 250]); // rename: 32
+    put_down_word(&mut deps, 2, vec![4, 251, 253, 250, 1, 2, 254, 252]); // foretime: 13
+    put_down_word(&mut deps, 3, vec![1, 253, 250, 254, 2, 4, 252]); // premade: 12
 
     let game_state_player1 = get_game_state(&mut deps, 1);
     assert!(
@@ -92,10 +91,10 @@ mod test {
 
     request_next_turn(&mut deps, 0);
 
-    send_bet(&mut deps, 0, Uint128(1_000_000));
     send_bet(&mut deps, 1, Uint128(1_000_000));
     send_bet(&mut deps, 0, Uint128(1_000_000));
     send_bet(&mut deps, 1, Uint128(1_000_000));
+    send_bet(&mut deps, 0, Uint128(1_000_000));
 
     // p0: t, g, a, a, r
     // p1: r, a, m, s, w
@@ -119,10 +118,10 @@ mod test {
 
     request_next_turn(&mut deps, 1);
 
-    send_bet(&mut deps, 0, Uint128(1_000_000));
     send_bet(&mut deps, 1, Uint128(1_000_000));
     send_bet(&mut deps, 0, Uint128(1_000_000));
     send_bet(&mut deps, 1, Uint128(1_000_000));
+    send_bet(&mut deps, 0, Uint128(1_000_000));
 
     // p0: a, n, n, d, e
     // p1: w, e, e, s, r
@@ -145,10 +144,10 @@ mod test {
 
     request_next_turn(&mut deps, 0);
 
-    send_bet(&mut deps, 0, Uint128(1_000_000));
     send_bet(&mut deps, 1, Uint128(1_000_000));
     send_bet(&mut deps, 0, Uint128(1_000_000));
     send_bet(&mut deps, 1, Uint128(1_000_000));
+    send_bet(&mut deps, 0, Uint128(1_000_000));
 
     // p0: i, l, g, f, d
     // p1: e, e, z, m, o
@@ -159,20 +158,14 @@ mod test {
     request_next_turn(&mut deps, 0);
 
     let final_bet = send_bet(&mut deps, 0, Uint128(1_000_000));
-    let mut p2 = mock_env(
-      "player2".to_string(),
-      &[Coin {
-        denom: "uscrt".to_string(),
-        amount: Uint128(1_000_000),
-      }],
-    );
+    let mut p2 = mock_env("player2".to_string(), &[]);
     p2.block.time = 0;
     let join_attempt = handle(
       &mut deps,
       p2,
       HandleMsg::Join {
         nfts: vec![],
-        secret: 2,
+        commitment: commitment_for(2),
         password: "".to_string(),
       },
     );
@@ -187,20 +180,14 @@ mod test {
   #[test]
   fn full_game_cant_be_joined() {
     let (_, mut deps) = init_with_4_players(false);
-    let mut p4 = mock_env(
-      "player4".to_string(),
-      &[Coin {
-        denom: "uscrt".to_string(),
-        amount: Uint128(1_000_000),
-      }],
-    );
+    let mut p4 = mock_env("player4".to_string(), &[]);
     p4.block.time = 0;
     let join_attempt = handle(
       &mut deps,
       p4,
       HandleMsg::Join {
         nfts: vec![],
-        secret: 4,
+        commitment: commitment_for(4),
         password: "".to_string(),
       },
     );
@@ -247,20 +234,14 @@ mod test {
   #[test]
   fn correct_password_needed_to_join_private_room() {
     let (_, mut deps) = init_with_2_players(true);
-    let mut p2 = mock_env(
-      "player2".to_string(),
-      &[Coin {
-        denom: "uscrt".to_string(),
-        amount: Uint128(1_000_000),
-      }],
-    );
+    let mut p2 = mock_env("player2".to_string(), &[]);
     p2.block.time = 0;
     let join_attempt = handle(
       &mut deps,
       p2.clone(),
       HandleMsg::Join {
         nfts: vec![],
-        secret: 4,
+        commitment: commitment_for(4),
         password: "wrong password".to_string(),
       },
     );
@@ -273,7 +254,7 @@ mod test {
       p2.clone(),
       HandleMsg::Join {
         nfts: vec![],
-        secret: 4,
+        commitment: commitment_for(4),
         password: "correct password".to_string(),
       },
     );
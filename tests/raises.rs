@@ -0,0 +1,78 @@
+mod utils;
+
+#[cfg(test)]
+#[allow(unused_must_use)]
+mod test {
+  use cosmwasm_std::Uint128;
+
+  use secret_dreamscape::game_state::{GameRound, TableConfig};
+
+  use crate::utils::*;
+
+  /// Test if a raise keeps the matching round open until the other players match it
+  #[test]
+  fn raise_keeps_matching_round_open() {
+    let (_, mut deps) = init_with_2_players(false);
+
+    send_bet(&mut deps, 0, Uint128(2_000_000));
+    send_bet(&mut deps, 1, Uint128(1_000_000));
+
+    // 1 SCRT to match player0 and 1 SCRT on top
+    let raised = raise(&mut deps, 1, Uint128(2_000_000));
+    assert!(raised.is_ok(), "Player couldn't raise");
+
+    let game = get_game_state(&mut deps, 0);
+    assert!(
+      game.round == GameRound::Matching,
+      "Matching round closed before the raise was matched"
+    );
+    assert_eq!(game.raises, 1, "Raise wasn't counted");
+    assert_eq!(game.pool, 5_000_000, "Raise wasn't added to the pool");
+
+    let matched = match_bet(&mut deps, 0, Uint128(1_000_000));
+    assert!(matched.is_ok(), "Player couldn't match the raise");
+
+    let game = get_game_state(&mut deps, 0);
+    assert!(game.round == GameRound::Flop, "Game round is not flop");
+    assert_eq!(game.raises, 0, "Raises weren't reset for the next round");
+  }
+
+  /// Test if a raise smaller than the table's minimum raise is rejected
+  #[test]
+  fn raise_below_minimum_is_rejected() {
+    let (_, mut deps) = init_with_2_players(false);
+
+    send_bet(&mut deps, 0, Uint128(2_000_000));
+    send_bet(&mut deps, 1, Uint128(1_000_000));
+
+    let raised = raise(&mut deps, 1, Uint128(1_100_000));
+    assert!(raised.is_err(), "Raise below the minimum was accepted");
+  }
+
+  /// Test if no more raises are accepted once the round's cap is reached
+  #[test]
+  fn raises_are_capped_per_round() {
+    let mut init_msg = default_init_msg(false);
+    init_msg.table = Some(TableConfig {
+      max_raises: 1,
+      ..TableConfig::default()
+    });
+    let (_, mut deps) = init_with_n_players_and_msg(2, init_msg);
+
+    send_bet(&mut deps, 0, Uint128(2_000_000));
+    send_bet(&mut deps, 1, Uint128(1_000_000));
+    raise(&mut deps, 1, Uint128(2_000_000));
+
+    let reraised = raise(&mut deps, 0, Uint128(2_000_000));
+    assert!(reraised.is_err(), "Raise over the cap was accepted");
+  }
+
+  /// Test if raising is only possible in the matching rounds
+  #[test]
+  fn cant_raise_outside_of_matching_rounds() {
+    let (_, mut deps) = init_with_2_players(false);
+
+    let raised = raise(&mut deps, 0, Uint128(2_000_000));
+    assert!(raised.is_err(), "Player raised during the blind round");
+  }
+}
@@ -0,0 +1,123 @@
+mod utils;
+
+#[cfg(test)]
+#[allow(unused_must_use)]
+mod test {
+  use cosmwasm_std::{BankMsg, Coin, CosmosMsg, HumanAddr, Uint128, WasmMsg};
+
+  use secret_dreamscape::game_state::GameRound;
+
+  use crate::utils::*;
+
+  /// Test if a secret that doesn't match the commitment is rejected
+  #[test]
+  fn wrong_secret_is_rejected() {
+    let (_, mut deps) = init_with_n_players(0, false);
+    join(&mut deps, 0, 0, false);
+    join(&mut deps, 1, 1, false);
+
+    let wrong_reveal = reveal(&mut deps, 0, 99);
+    assert!(wrong_reveal.is_err(), "Secret didn't have to match");
+
+    let reveal = reveal(&mut deps, 0, 0);
+    assert!(reveal.is_ok(), "Matching secret was rejected");
+  }
+
+  /// Test if the deck is only shuffled and dealt after every player revealed their secret and
+  /// bought in
+  #[test]
+  fn cards_are_dealt_after_everyone_revealed() {
+    let (_, mut deps) = init_with_n_players(0, false);
+    join(&mut deps, 0, 0, false);
+    join(&mut deps, 1, 1, false);
+    set_viewing_key(&mut deps, 0, "key0");
+    buy_chips(&mut deps, 0, Uint128(10_000_000));
+
+    reveal(&mut deps, 0, 0);
+    let game = get_game_state(&mut deps, 0);
+    assert!(game.round == GameRound::Reveal, "Round is not reveal");
+    assert!(game.hand.is_empty(), "Cards were dealt before every reveal");
+
+    let bet = send_bet(&mut deps, 0, Uint128(1_000_000));
+    assert!(bet.is_err(), "Player could bet before the cards were dealt");
+
+    reveal(&mut deps, 1, 1);
+    let game = get_game_state(&mut deps, 0);
+    assert!(
+      game.hand.is_empty(),
+      "Cards were dealt before everyone bought in"
+    );
+
+    buy_chips(&mut deps, 1, Uint128(10_000_000));
+    let game = get_game_state(&mut deps, 0);
+    assert!(game.round == GameRound::Blind, "Round is not blind");
+    assert_eq!(game.hand.len(), 5, "Cards weren't dealt");
+  }
+
+  /// Test if a player who never reveals is removed after the deadline and loses the minimum buy
+  #[test]
+  fn player_who_doesnt_reveal_is_removed_with_penalty() {
+    let (_, mut deps) = init_with_n_players(0, false);
+    for i in 0..3 {
+      join(&mut deps, i, i as u64, false);
+      buy_chips(&mut deps, i, Uint128(10_000_000));
+    }
+    set_viewing_key(&mut deps, 0, "key0");
+    reveal(&mut deps, 0, 0);
+    reveal(&mut deps, 1, 1);
+
+    let claim = claim_timeout(&mut deps, 0, 120);
+    assert!(claim.is_err(), "Timeout was claimed before the deadline");
+
+    let claim = claim_timeout(&mut deps, 0, 121).unwrap();
+    match claim.messages[0].clone() {
+      CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr,
+        send,
+        ..
+      }) => {
+        assert_eq!(
+          contract_addr,
+          HumanAddr("jackpot".to_string()),
+          "Penalty was not sent to the jackpot"
+        );
+        assert_eq!(send[0], Coin::new(1_000_000, "uscrt"));
+      }
+      _ => panic!("Expected jackpot funding message"),
+    }
+    match claim.messages[1].clone() {
+      CosmosMsg::Bank(BankMsg::Send {
+        to_address, amount, ..
+      }) => {
+        assert_eq!(to_address, HumanAddr("player2".to_string()));
+        assert_eq!(amount[0], Coin::new(9_000_000, "uscrt"));
+      }
+      _ => panic!("Expected the rest of the chips to be refunded"),
+    }
+
+    let game = get_game_state(&mut deps, 0);
+    assert_eq!(game.players.len(), 2, "Player wasn't removed");
+    assert!(game.round == GameRound::Blind, "Round is not blind");
+    assert_eq!(game.hand.len(), 5, "Cards weren't dealt");
+  }
+  /// Test if a player who revealed but never bought in is removed after the deadline
+  #[test]
+  fn player_who_doesnt_buy_in_is_removed() {
+    let (_, mut deps) = init_with_n_players(0, false);
+    for i in 0..3 {
+      join(&mut deps, i, i as u64, false);
+      reveal(&mut deps, i, i as u64);
+    }
+    set_viewing_key(&mut deps, 0, "key0");
+    buy_chips(&mut deps, 0, Uint128(10_000_000));
+    buy_chips(&mut deps, 1, Uint128(10_000_000));
+
+    let claim = claim_timeout(&mut deps, 0, 121).unwrap();
+    assert!(claim.messages.is_empty(), "Player without chips paid");
+
+    let game = get_game_state(&mut deps, 0);
+    assert_eq!(game.players.len(), 2, "Player wasn't removed");
+    assert!(game.round == GameRound::Blind, "Round is not blind");
+    assert_eq!(game.hand.len(), 5, "Cards weren't dealt");
+  }
+}
@@ -0,0 +1,125 @@
+mod utils;
+
+#[cfg(test)]
+#[allow(unused_must_use)]
+mod test {
+  use cosmwasm_std::{HumanAddr, Uint128};
+
+  use secret_dreamscape::game_state::{GameRound, PlayerAction};
+
+  use crate::utils::*;
+
+  /// Test if a player who can't cover a match can call with the rest of their chips
+  #[test]
+  fn short_stack_can_match_all_in() {
+    let mut deps = init_with_chips(vec![10_000_000, 2_000_000]);
+
+    send_bet(&mut deps, 0, Uint128(5_000_000));
+    send_bet(&mut deps, 1, Uint128(1_000_000));
+
+    let partial_match = match_bet(&mut deps, 1, Uint128(500_000));
+    assert!(
+      partial_match.is_err(),
+      "Player matched with less than all of their chips"
+    );
+
+    let all_in = match_bet(&mut deps, 1, Uint128(1_000_000));
+    assert!(all_in.is_ok(), "Player couldn't go all-in");
+
+    let game = get_game_state(&mut deps, 0);
+    assert!(
+      game.players[1].last_action == Some(PlayerAction::AllIn(1_000_000)),
+      "Player isn't all-in"
+    );
+    // nobody is left to bet against player0, so the flop is skipped
+    assert!(game.round == GameRound::Choice, "Game round is not choice");
+    assert_eq!(game.pots.len(), 2, "Side pot wasn't created");
+    assert_eq!(game.pots[0].amount, 4_000_000, "Main pot is wrong");
+    assert_eq!(game.pots[0].eligible.len(), 2, "Main pot isn't shared");
+    assert_eq!(game.pots[1].amount, 3_000_000, "Side pot is wrong");
+    assert!(
+      game.pots[1].eligible == vec![HumanAddr("player0".to_string())],
+      "All-in player can win the side pot"
+    );
+
+    // p0: y, t, g, c, l
+    // p1: r, t, i, a, d
+    // river: l, i, n, a, b
+    put_down_word(&mut deps, 0, vec![0]); // y: 0
+    put_down_word(&mut deps, 1, vec![254, 253, 252, 4, 2, 1]); // bandit: 9
+
+    let game = get_game_state(&mut deps, 0);
+    assert_eq!(
+      game.players[1].chips, 3_600_000,
+      "All-in winner didn't get the main pot"
+    );
+    // nobody could call the side pot, so it comes back without rake
+    assert_eq!(
+      game.players[0].chips, 8_000_000,
+      "Uncalled bet wasn't returned in full"
+    );
+  }
+
+  /// Test if a raise nobody called goes back to the raiser before the rake is taken
+  #[test]
+  fn uncalled_raise_isnt_raked() {
+    let mut deps = init_with_chips(vec![10_000_000, 10_000_000]);
+
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    send_bet(&mut deps, 1, Uint128(3_000_000));
+    fold(&mut deps, 0);
+
+    let game = get_game_state(&mut deps, 0);
+    // 2_000_000 of the raise comes back, the called 2_000_000 is raked
+    assert_eq!(
+      game.players[1].chips, 10_800_000,
+      "Uncalled raise was raked"
+    );
+    assert_eq!(game.players[0].chips, 9_000_000);
+  }
+
+  /// Test if every pot is won by the best word among the players eligible for it
+  #[test]
+  fn each_pot_goes_to_its_best_eligible_word() {
+    let mut deps = init_with_chips(vec![10_000_000, 2_000_000, 10_000_000]);
+
+    send_bet(&mut deps, 0, Uint128(5_000_000));
+    send_bet(&mut deps, 1, Uint128(2_000_000));
+    send_bet(&mut deps, 2, Uint128(5_000_000));
+
+    let game = get_game_state(&mut deps, 0);
+    assert!(game.round == GameRound::Flop, "Game round is not flop");
+
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    send_bet(&mut deps, 2, Uint128(1_000_000));
+
+    let game = get_game_state(&mut deps, 0);
+    assert!(game.round == GameRound::Choice, "Game round is not choice");
+    assert_eq!(game.pots[0].amount, 6_000_000, "Main pot is wrong");
+    assert_eq!(game.pots[1].amount, 8_000_000, "Side pot is wrong");
+
+    // p0: v, d, d, j, u
+    // p1: a, e, a, e, a
+    // p2: o, v, r, h, c
+    // river: w, t, y, s, r
+    put_down_word(&mut deps, 0, vec![3, 4, 251]); // jut: 10
+    put_down_word(&mut deps, 1, vec![250, 0, 251, 1, 254, 252]); // watery: 12
+    put_down_word(&mut deps, 2, vec![250, 0, 2, 251, 3]); // worth: 11
+
+    let game = get_game_state(&mut deps, 0);
+    assert_eq!(
+      game.winner,
+      Some(HumanAddr("player1".to_string())),
+      "Best word didn't win the hand"
+    );
+    assert_eq!(
+      game.players[1].chips, 5_400_000,
+      "All-in player didn't win the main pot"
+    );
+    assert_eq!(
+      game.players[2].chips, 11_200_000,
+      "Best eligible word didn't win the side pot"
+    );
+    assert_eq!(game.players[0].chips, 4_000_000, "Loser won chips");
+  }
+}
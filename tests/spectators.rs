@@ -0,0 +1,60 @@
+mod utils;
+
+#[cfg(test)]
+#[allow(unused_must_use)]
+mod test {
+  use cosmwasm_std::Uint128;
+
+  use secret_dreamscape::game_state::GameRound;
+
+  use crate::utils::*;
+
+  /// Test if anyone can follow the table without a seat or a viewing key
+  #[test]
+  fn spectator_can_follow_the_table() {
+    let (_, mut deps) = init_with_2_players(false);
+    let table = get_public_state(&mut deps);
+    assert_eq!(table.players.len(), 2, "Players weren't listed");
+    assert!(table.round == GameRound::Blind, "Round isn't blind");
+    assert!(table.river.is_none(), "River was shown before the flop");
+
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    send_bet(&mut deps, 1, Uint128(1_000_000));
+    let table = get_public_state(&mut deps);
+    assert!(table.round == GameRound::Flop, "Round isn't the flop");
+    assert_eq!(table.pool, 2_000_000, "Pool doesn't match the bets");
+    assert_eq!(
+      table.river.map(|river| river.len()),
+      Some(5),
+      "River wasn't shown after the flop"
+    );
+  }
+
+  /// Test if spectators only see the words once everyone has put theirs down
+  #[test]
+  fn spectator_sees_words_once_all_are_down() {
+    let (_, mut deps) = init_with_2_players(false);
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    send_bet(&mut deps, 1, Uint128(1_000_000));
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    send_bet(&mut deps, 1, Uint128(1_000_000));
+
+    // p0: y, t, g, c, l
+    // p1: r, t, i, a, d
+    // river: l, i, n, a, b
+    put_down_word(&mut deps, 0, vec![254, 251, 250, 4, 0, 3, 253, 252]); // billycan: 15
+    let table = get_public_state(&mut deps);
+    assert!(table.words.is_empty(), "Word was shown before the showdown");
+
+    put_down_word(&mut deps, 1, vec![254, 253, 252, 4, 2, 1]); // bandit: 9
+    let table = get_public_state(&mut deps);
+    assert_eq!(
+      table.words.len(),
+      2,
+      "Words weren't shown after the showdown"
+    );
+    assert!(table.words.iter().all(|w| w.visible));
+    assert_eq!(table.words[0].points, 15);
+    assert!(table.winner.is_some(), "Winner wasn't shown");
+  }
+}
@@ -0,0 +1,93 @@
+mod utils;
+
+#[cfg(test)]
+#[allow(unused_must_use)]
+mod test {
+  use cosmwasm_std::testing::{mock_env, MockApi, MockQuerier, MockStorage};
+  use cosmwasm_std::{CosmosMsg, Extern, HandleResponse, Uint128, WasmMsg};
+
+  use secret_dreamscape::contract::{handle, HandleMsg, SecretDreamscapeNFT};
+  use secret_dreamscape::game_state::TableConfig;
+
+  use crate::utils::*;
+
+  // player 0 owns the nft of the t, both bet through to the round words are put down in
+  fn init_with_nft() -> Extern<MockStorage, MockApi, MockQuerier> {
+    let mut msg = default_init_msg(false);
+    msg.table = Some(TableConfig {
+      gold_cards: 0,
+      ..TableConfig::default()
+    });
+    let (_, mut deps) = init_with_n_players_and_msg(0, msg);
+
+    let mut env = mock_env("player0", &[]);
+    env.block.time = 0;
+    handle(
+      &mut deps,
+      env,
+      HandleMsg::Join {
+        nfts: vec![SecretDreamscapeNFT {
+          id: "nft_t".to_string(),
+          letter: "T".to_string(),
+          gold: false,
+        }],
+        commitment: commitment_for(0),
+        password: "".to_string(),
+      },
+    );
+    join(&mut deps, 1, 1, false);
+    for i in 0..2 {
+      reveal(&mut deps, i, i as u64);
+      buy_chips(&mut deps, i, Uint128(10_000_000));
+      set_viewing_key(&mut deps, i, &format!("key{}", i));
+    }
+
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    send_bet(&mut deps, 1, Uint128(1_000_000));
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    send_bet(&mut deps, 1, Uint128(1_000_000));
+    deps
+  }
+
+  fn stamps(response: &HandleResponse) -> Vec<String> {
+    let mut stamps = vec![];
+    for message in response.messages.iter() {
+      if let CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) = message {
+        let msg = String::from_utf8(msg.0.clone()).unwrap();
+        if msg.starts_with("{\"stamp\":") {
+          stamps.push(msg);
+        }
+      }
+    }
+    stamps
+  }
+
+  /// Test if the nft is stamped with the whole id of the word, past what fits in a u16
+  #[test]
+  fn stamp_keeps_the_whole_word_id() {
+    let mut deps = init_with_nft();
+
+    // p0: y, t, g, c, l
+    // river: l, i, n, a, b
+    let response = put_down_word(&mut deps, 0, vec![1, 253, 254]).unwrap(); // tab
+    let stamps = stamps(&response);
+    assert_eq!(stamps.len(), 1, "Nft wasn't stamped");
+    assert!(stamps[0].contains("\"nft_id\":\"nft_t\""));
+    assert!(
+      stamps[0].contains("\"word_id\":72004"),
+      "Word id was cut short"
+    );
+  }
+
+  /// Test if nothing is stamped for a word that isn't in the dictionary
+  #[test]
+  fn invalid_word_isnt_stamped() {
+    let mut deps = init_with_nft();
+
+    let response = put_down_word(&mut deps, 0, vec![0, 1]).unwrap(); // yt
+    assert!(
+      stamps(&response).is_empty(),
+      "Word outside the dictionary was stamped"
+    );
+  }
+}
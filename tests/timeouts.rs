@@ -0,0 +1,190 @@
+mod utils;
+
+#[cfg(test)]
+#[allow(unused_must_use)]
+mod test {
+  use cosmwasm_std::{HumanAddr, Uint128};
+
+  use secret_dreamscape::game_state::{GameRound, HandOutcome, PlayerAction};
+
+  use crate::utils::*;
+
+  // block time used by mock_env for every action after joining
+  const NOW: u64 = 1_571_797_419;
+
+  /// Test if the deadline of the current round is exposed and moves when the round changes
+  #[test]
+  fn round_deadline_is_exposed() {
+    let (_, mut deps) = init_with_2_players(false);
+
+    let game = get_game_state(&mut deps, 0);
+    // buying in is what put the action on the first player
+    assert_eq!(
+      game.round_deadline,
+      Some(NOW + 120),
+      "Deadline wasn't set when the hand started"
+    );
+
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    send_bet(&mut deps, 1, Uint128(1_000_000));
+
+    let game = get_game_state(&mut deps, 0);
+    assert!(game.round == GameRound::Flop, "Game round is not flop");
+    assert_eq!(
+      game.round_deadline,
+      Some(NOW + 120),
+      "Deadline didn't move with the round"
+    );
+  }
+
+  /// Test if a timeout can't be claimed before the deadline passes
+  #[test]
+  fn cant_claim_timeout_before_deadline() {
+    let (_, mut deps) = init_with_2_players(false);
+
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    send_bet(&mut deps, 1, Uint128(1_000_000));
+
+    let claim = claim_timeout(&mut deps, 0, NOW + 120);
+    assert!(claim.is_err(), "Timeout was claimed before the deadline");
+  }
+
+  /// Test if only seated players can claim a timeout
+  #[test]
+  fn only_players_can_claim_timeout() {
+    let (_, mut deps) = init_with_2_players(false);
+
+    let claim = claim_timeout(&mut deps, 5, NOW);
+    assert!(claim.is_err(), "Spectator claimed a timeout");
+  }
+
+  /// Test if an idle player is checked during the blind and folded when they had to match
+  #[test]
+  fn idle_player_is_checked_then_folded() {
+    let (_, mut deps) = init_with_2_players(false);
+
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    claim_timeout(&mut deps, 0, NOW + 121).unwrap();
+
+    let game = get_game_state(&mut deps, 0);
+    assert!(
+      game.round == GameRound::Matching,
+      "Idle player wasn't checked"
+    );
+
+    claim_timeout(&mut deps, 0, NOW + 242).unwrap();
+
+    let game = get_game_state(&mut deps, 0);
+    assert!(game.players[1].folded, "Idle player wasn't folded");
+    assert_eq!(
+      game.winner,
+      Some(HumanAddr("player0".to_string())),
+      "Remaining player didn't win the hand"
+    );
+  }
+
+  /// Test if the deadline moves with the action, not only with the round
+  #[test]
+  fn deadline_moves_with_the_action() {
+    let (_, mut deps) = init_with_4_players(false);
+
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+
+    let game = get_game_state(&mut deps, 0);
+    assert!(game.round == GameRound::Blind, "Game round is not blind");
+    assert_eq!(
+      game.round_deadline,
+      Some(NOW + 120),
+      "Deadline didn't move with the action"
+    );
+  }
+
+  /// Test if only the player the action is on is timed out
+  #[test]
+  fn only_player_on_action_is_timed_out() {
+    let (_, mut deps) = init_with_4_players(false);
+
+    claim_timeout(&mut deps, 1, NOW + 121).unwrap();
+
+    let game = get_game_state(&mut deps, 0);
+    assert!(
+      game.players[0].last_action == Some(PlayerAction::Checked),
+      "Player on the action wasn't checked"
+    );
+    for i in 1..4 {
+      assert!(
+        game.players[i].last_action.is_none(),
+        "Player {} was timed out before it was their turn",
+        i
+      );
+    }
+    assert_eq!(player_on_action(&mut deps), Some(1));
+
+    let claim = claim_timeout(&mut deps, 0, NOW + 121);
+    assert!(claim.is_err(), "Next player had no time to act");
+  }
+
+  /// Test if a player that doesn't put down a word is folded and the showdown is resolved
+  #[test]
+  fn idle_player_in_choice_is_folded() {
+    let (_, mut deps) = init_with_2_players(false);
+
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    send_bet(&mut deps, 1, Uint128(1_000_000));
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    send_bet(&mut deps, 1, Uint128(1_000_000));
+
+    // p0: y, t, g, c, l
+    // river: l, i, n, a, b
+    put_down_word(&mut deps, 0, vec![254, 251, 250, 4, 0, 3, 253, 252]); // billycan: 15
+    claim_timeout(&mut deps, 0, NOW + 121).unwrap();
+
+    let game = get_game_state(&mut deps, 0);
+    assert!(game.players[1].folded, "Idle player wasn't folded");
+    assert_eq!(game.players[1].hp, 5, "Idle player lost hp by folding");
+    assert_eq!(
+      game.winner,
+      Some(HumanAddr("player0".to_string())),
+      "Remaining player didn't win the hand"
+    );
+    assert!(
+      claim_timeout(&mut deps, 0, NOW + 1_000).is_err(),
+      "Timeout was claimed after the hand was over"
+    );
+  }
+
+  /// Test if the hand ends and the bets are returned when nobody puts down a word in time
+  #[test]
+  fn hand_without_words_is_refunded() {
+    let (_, mut deps) = init_with_2_players(false);
+
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    send_bet(&mut deps, 1, Uint128(1_000_000));
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    send_bet(&mut deps, 1, Uint128(1_000_000));
+
+    let claim = claim_timeout(&mut deps, 0, NOW + 121);
+    assert!(claim.is_ok(), "Timeout without any word wasn't claimed");
+
+    let game = get_game_state(&mut deps, 0);
+    assert!(
+      game.players.iter().all(|p| p.folded),
+      "Idle players weren't folded"
+    );
+    assert!(game.winner.is_none(), "Refunded hand had a winner");
+    let history = get_hand_history(&mut deps, 0, 10);
+    assert!(
+      history.hands[0].outcome == Some(HandOutcome::Refunded),
+      "Hand didn't end"
+    );
+    for player in game.players.iter() {
+      assert_eq!(player.chips, 10_000_000, "Bet wasn't returned");
+      assert_eq!(player.hp, 5, "Player lost hp without a showdown");
+    }
+
+    let next = request_next_turn(&mut deps, 0);
+    assert!(next.is_ok(), "Next hand couldn't be dealt");
+    let game = get_game_state(&mut deps, 0);
+    assert!(game.round == GameRound::Blind, "Next hand didn't start");
+  }
+}
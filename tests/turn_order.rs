@@ -0,0 +1,85 @@
+mod utils;
+
+#[cfg(test)]
+#[allow(unused_must_use)]
+mod test {
+  use cosmwasm_std::{HumanAddr, Uint128};
+
+  use secret_dreamscape::game_state::GameRound;
+
+  use crate::utils::*;
+
+  /// Test if only the player the action is on can act, going around the table
+  #[test]
+  fn out_of_turn_actions_are_rejected() {
+    let mut deps = init_with_chips(vec![5_000_000, 5_000_000, 5_000_000]);
+    assert_eq!(
+      player_on_action(&mut deps),
+      Some(0),
+      "First seat didn't open"
+    );
+
+    let early_bet = send_bet(&mut deps, 1, Uint128(1_000_000));
+    assert!(early_bet.is_err(), "Player bet out of turn");
+    let early_fold = fold(&mut deps, 2);
+    assert!(early_fold.is_err(), "Player folded out of turn");
+
+    send_bet(&mut deps, 0, Uint128(1_000_000));
+    assert_eq!(
+      player_on_action(&mut deps),
+      Some(1),
+      "Action didn't move on"
+    );
+    send_bet(&mut deps, 1, Uint128(2_000_000));
+    send_bet(&mut deps, 2, Uint128(1_000_000));
+
+    let game = get_game_state(&mut deps, 0);
+    assert!(game.round == GameRound::Matching, "Round is not matching");
+    // player1 already has the highest bet, so only player0 and player2 have to match
+    assert_eq!(player_on_action(&mut deps), Some(0));
+    let early_match = match_bet(&mut deps, 2, Uint128(1_000_000));
+    assert!(early_match.is_err(), "Player matched out of turn");
+    match_bet(&mut deps, 0, Uint128(1_000_000));
+    assert_eq!(
+      player_on_action(&mut deps),
+      Some(2),
+      "Player at the highest bet had to act"
+    );
+  }
+
+  /// Test if the dealer button moves every hand and the action starts after it
+  #[test]
+  fn dealer_button_rotates() {
+    let (_, mut deps) = init_with_2_players(false);
+    let game = get_game_state(&mut deps, 0);
+    assert_eq!(game.dealer, 1, "Button didn't start on the last seat");
+    assert_eq!(game.action_on, Some(HumanAddr("player0".to_string())));
+
+    fold(&mut deps, 0);
+    let game = get_game_state(&mut deps, 0);
+    assert!(game.action_on.is_none(), "Action stayed on after the hand");
+
+    request_next_turn(&mut deps, 0);
+    let game = get_game_state(&mut deps, 0);
+    assert_eq!(game.dealer, 0, "Button didn't move");
+    assert_eq!(game.action_on, Some(HumanAddr("player1".to_string())));
+  }
+
+  /// Test if a new player takes the lowest free seat
+  #[test]
+  fn lowest_free_seat_is_taken() {
+    let mut deps = init_with_chips(vec![5_000_000, 5_000_000, 5_000_000]);
+    fold(&mut deps, 0);
+    fold(&mut deps, 1);
+    leave(&mut deps, 1);
+
+    join(&mut deps, 3, 3, false);
+    let game = get_game_state(&mut deps, 0);
+    let seats: Vec<u8> = game.players.iter().map(|p| p.seat).collect();
+    assert_eq!(seats, vec![0, 1, 2], "Seats aren't in order");
+    assert!(
+      game.players[1].waiting,
+      "New player wasn't seated in the free seat"
+    );
+  }
+}
@@ -1,43 +1,61 @@
+#![allow(dead_code, unused_must_use)]
+
 use cosmwasm_std::testing::{mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage};
-use cosmwasm_std::{from_binary, Coin, Extern, HandleResult, InitResponse, StdResult, Uint128};
+use cosmwasm_std::{
+  from_binary, Binary, Coin, Extern, HandleResult, HumanAddr, InitResponse, StdResult, Uint128,
+};
+use sha2::{Digest, Sha256};
 
 use secret_dreamscape::contract::{handle, init, HandleMsg, InitMsg};
-use secret_dreamscape::query::{query, CanJoinResponse, GameState, QueryMsg};
+use secret_dreamscape::game_state::Dictionary;
+use secret_dreamscape::query::{
+  query, CanJoinResponse, ConfigResponse, GameState, HandHistoryResponse, PublicState, QueryMsg,
+};
 
-fn init_without_password() -> (
-  StdResult<InitResponse>,
-  Extern<MockStorage, MockApi, MockQuerier>,
-) {
-  let mut deps = mock_dependencies(20, &[]);
-  let env = mock_env("player0", &[]);
+// the default dictionary without blanks, the deck the fixture hands are dealt from
+pub fn fixture_dictionary() -> Dictionary {
+  Dictionary {
+    blanks: 0,
+    ..Dictionary::default()
+  }
+}
 
+pub fn default_init_msg(private: bool) -> InitMsg {
   let stamp_addr = mock_env("stamp", &[]);
 
-  let init_msg = InitMsg {
+  InitMsg {
     stamp_addr: stamp_addr.contract.address.clone(),
     stamp_hash: stamp_addr.contract_code_hash,
     bg: 0,
-    password: None,
-  };
-
-  (init(&mut deps, env, init_msg), deps)
+    password: if private {
+      Some("correct password".to_string())
+    } else {
+      None
+    },
+    label: "table".to_string(),
+    callback_addr: HumanAddr("phonebook".to_string()),
+    callback_hash: "".to_string(),
+    min_buy: 1_000_000,
+    max_buy: 10_000_000,
+    jackpot_addr: HumanAddr("jackpot".to_string()),
+    jackpot_hash: "".to_string(),
+    table: None,
+    currency: None,
+    dictionary: Some(fixture_dictionary()),
+    admin: None,
+  }
 }
 
-fn init_with_password() -> (
+fn init_with_msg(
+  init_msg: InitMsg,
+) -> (
   StdResult<InitResponse>,
   Extern<MockStorage, MockApi, MockQuerier>,
 ) {
   let mut deps = mock_dependencies(20, &[]);
-  let env = mock_env("player0", &[]);
-
-  let stamp_addr = mock_env("stamp", &[]);
-
-  let init_msg = InitMsg {
-    stamp_addr: stamp_addr.contract.address.clone(),
-    stamp_hash: stamp_addr.contract_code_hash,
-    bg: 0,
-    password: Some("correct password".to_string()),
-  };
+  let mut env = mock_env("player0", &[]);
+  // keeps the deck seed, and so the dealt cards, the same across test runs
+  env.block.time = 0;
 
   (init(&mut deps, env, init_msg), deps)
 }
@@ -67,42 +85,145 @@ pub fn init_with_n_players(
   StdResult<InitResponse>,
   Extern<MockStorage, MockApi, MockQuerier>,
 ) {
-  let (init_result, mut deps) = if private {
-    init_with_password()
-  } else {
-    init_without_password()
-  };
+  init_with_n_players_and_msg(n, default_init_msg(private))
+}
+
+pub fn init_with_n_players_and_msg(
+  n: u64,
+  init_msg: InitMsg,
+) -> (
+  StdResult<InitResponse>,
+  Extern<MockStorage, MockApi, MockQuerier>,
+) {
+  let private = init_msg.password.is_some();
+  let (init_result, mut deps) = init_with_msg(init_msg);
+  // everyone joins before anyone reveals, so all of them are dealt into the first hand
   for i in 0..n {
-    let mut player_env = mock_env(
-      format!("player{}", i),
-      &[Coin {
-        denom: "uscrt".to_string(),
-        amount: Uint128(1_000_000),
-      }],
-    );
-    player_env.block.time = 0;
-    handle(
-      &mut deps,
-      player_env,
-      HandleMsg::Join {
-        nfts: vec![],
-        secret: i,
-        password: if private { "correct password" } else { "" }.to_string(),
-      },
-    );
+    join(&mut deps, i as usize, i, private);
+  }
+  for i in 0..n {
+    reveal(&mut deps, i as usize, i);
+  }
+  for i in 0..n {
+    buy_chips(&mut deps, i as usize, Uint128(10_000_000));
+    set_viewing_key(&mut deps, i as usize, &format!("key{}", i));
   }
 
   (init_result, deps)
 }
 
+pub fn init_with_chips(chips: Vec<u128>) -> Extern<MockStorage, MockApi, MockQuerier> {
+  init_with_chips_and_msg(chips, default_init_msg(false))
+}
+
+pub fn init_with_chips_and_msg(
+  chips: Vec<u128>,
+  init_msg: InitMsg,
+) -> Extern<MockStorage, MockApi, MockQuerier> {
+  let (_, mut deps) = init_with_n_players_and_msg(0, init_msg);
+  for i in 0..chips.len() {
+    join(&mut deps, i, i as u64, false);
+  }
+  for (i, amount) in chips.iter().enumerate() {
+    reveal(&mut deps, i, i as u64);
+    buy_chips(&mut deps, i, Uint128(*amount));
+    set_viewing_key(&mut deps, i, &format!("key{}", i));
+  }
+  deps
+}
+
+pub fn join(
+  deps: &mut Extern<MockStorage, MockApi, MockQuerier>,
+  player: usize,
+  secret: u64,
+  private: bool,
+) -> HandleResult {
+  let mut player_env = mock_env(format!("player{}", player), &[]);
+  player_env.block.time = 0;
+  handle(
+    deps,
+    player_env,
+    HandleMsg::Join {
+      nfts: vec![],
+      commitment: commitment_for(secret),
+      password: if private { "correct password" } else { "" }.to_string(),
+    },
+  )
+}
+
+pub fn commitment_for(secret: u64) -> Binary {
+  Binary(Sha256::digest(&secret.to_be_bytes()).to_vec())
+}
+
+pub fn reveal(
+  deps: &mut Extern<MockStorage, MockApi, MockQuerier>,
+  player: usize,
+  secret: u64,
+) -> HandleResult {
+  let mut env = mock_env(format!("player{}", player), &[]);
+  env.block.time = 0;
+  handle(
+    deps,
+    env,
+    HandleMsg::Reveal {
+      secret: Binary(secret.to_be_bytes().to_vec()),
+    },
+  )
+}
+
 pub fn get_game_state(
   deps: &mut Extern<MockStorage, MockApi, MockQuerier>,
   player: u64,
 ) -> GameState {
-  let query_data = query(deps, QueryMsg::GetGameState { secret: player });
+  let query_data = query(
+    deps,
+    QueryMsg::GetGameState {
+      address: HumanAddr(format!("player{}", player)),
+      key: format!("key{}", player),
+    },
+  );
+  from_binary(&query_data.unwrap()).unwrap()
+}
+
+pub fn get_public_state(deps: &mut Extern<MockStorage, MockApi, MockQuerier>) -> PublicState {
+  let query_data = query(deps, QueryMsg::GetPublicState {});
   from_binary(&query_data.unwrap()).unwrap()
 }
 
+pub fn get_hand_history(
+  deps: &mut Extern<MockStorage, MockApi, MockQuerier>,
+  start: u32,
+  limit: u32,
+) -> HandHistoryResponse {
+  let query_data = query(deps, QueryMsg::GetHandHistory { start, limit });
+  from_binary(&query_data.unwrap()).unwrap()
+}
+
+pub fn get_player_hand_history(
+  deps: &mut Extern<MockStorage, MockApi, MockQuerier>,
+  player: u64,
+  start: u32,
+  limit: u32,
+) -> HandHistoryResponse {
+  let query_data = query(
+    deps,
+    QueryMsg::GetPlayerHandHistory {
+      address: HumanAddr(format!("player{}", player)),
+      key: format!("key{}", player),
+      start,
+      limit,
+    },
+  );
+  from_binary(&query_data.unwrap()).unwrap()
+}
+
+// index of the player the action is on, as in player{index}
+pub fn player_on_action(deps: &mut Extern<MockStorage, MockApi, MockQuerier>) -> Option<usize> {
+  get_game_state(deps, 0)
+    .action_on
+    .map(|addr| addr.as_str().trim_start_matches("player").parse().unwrap())
+}
+
 pub fn get_join_permissions(
   deps: &mut Extern<MockStorage, MockApi, MockQuerier>,
 ) -> CanJoinResponse {
@@ -110,11 +231,15 @@ pub fn get_join_permissions(
   from_binary(&query_data.unwrap()).unwrap()
 }
 
-fn transaction_with_money(
+pub fn get_config(deps: &mut Extern<MockStorage, MockApi, MockQuerier>) -> ConfigResponse {
+  let query_data = query(deps, QueryMsg::GetConfig {});
+  from_binary(&query_data.unwrap()).unwrap()
+}
+
+pub fn buy_chips(
   deps: &mut Extern<MockStorage, MockApi, MockQuerier>,
   player: usize,
   amount: Uint128,
-  msg: HandleMsg,
 ) -> HandleResult {
   handle(
     deps,
@@ -125,7 +250,7 @@ fn transaction_with_money(
         amount,
       }],
     ),
-    msg,
+    HandleMsg::BuyChips {},
   )
 }
 
@@ -134,7 +259,13 @@ pub fn send_bet(
   player: usize,
   amount: Uint128,
 ) -> HandleResult {
-  transaction_with_money(deps, player, amount, HandleMsg::Bet {})
+  handle(
+    deps,
+    mock_env(format!("player{}", player), &[]),
+    HandleMsg::Bet {
+      amount: amount.u128() as u64,
+    },
+  )
 }
 
 pub fn match_bet(
@@ -142,7 +273,27 @@ pub fn match_bet(
   player: usize,
   amount: Uint128,
 ) -> HandleResult {
-  transaction_with_money(deps, player, amount, HandleMsg::Match {})
+  handle(
+    deps,
+    mock_env(format!("player{}", player), &[]),
+    HandleMsg::Match {
+      amount: amount.u128() as u64,
+    },
+  )
+}
+
+pub fn raise(
+  deps: &mut Extern<MockStorage, MockApi, MockQuerier>,
+  player: usize,
+  amount: Uint128,
+) -> HandleResult {
+  handle(
+    deps,
+    mock_env(format!("player{}", player), &[]),
+    HandleMsg::Raise {
+      amount: amount.u128() as u64,
+    },
+  )
 }
 
 pub fn put_down_word(
@@ -156,6 +307,8 @@ pub fn put_down_word(
     HandleMsg::PutDownCard {
       indexes: word,
       opened_dictionary: false,
+      proof: None,
+      blank_letters: vec![],
     },
   )
 }
@@ -178,3 +331,47 @@ pub fn fold(deps: &mut Extern<MockStorage, MockApi, MockQuerier>, player: usize)
     HandleMsg::Fold {},
   )
 }
+
+pub fn cash_out(
+  deps: &mut Extern<MockStorage, MockApi, MockQuerier>,
+  player: usize,
+  amount: u64,
+) -> HandleResult {
+  handle(
+    deps,
+    mock_env(format!("player{}", player), &[]),
+    HandleMsg::CashOut { amount },
+  )
+}
+
+pub fn leave(deps: &mut Extern<MockStorage, MockApi, MockQuerier>, player: usize) -> HandleResult {
+  handle(
+    deps,
+    mock_env(format!("player{}", player), &[]),
+    HandleMsg::Leave {},
+  )
+}
+
+pub fn claim_timeout(
+  deps: &mut Extern<MockStorage, MockApi, MockQuerier>,
+  player: usize,
+  time: u64,
+) -> HandleResult {
+  let mut env = mock_env(format!("player{}", player), &[]);
+  env.block.time = time;
+  handle(deps, env, HandleMsg::ClaimTimeout {})
+}
+
+pub fn set_viewing_key(
+  deps: &mut Extern<MockStorage, MockApi, MockQuerier>,
+  player: usize,
+  key: &str,
+) -> HandleResult {
+  handle(
+    deps,
+    mock_env(format!("player{}", player), &[]),
+    HandleMsg::SetViewingKey {
+      key: key.to_string(),
+    },
+  )
+}
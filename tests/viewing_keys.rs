@@ -0,0 +1,155 @@
+mod utils;
+
+#[cfg(test)]
+#[allow(unused_must_use)]
+mod test {
+  use cosmwasm_std::testing::{mock_env, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+  use cosmwasm_std::{from_binary, Binary, Extern, HumanAddr, StdResult};
+
+  use secret_dreamscape::contract::{handle, HandleAnswer, HandleMsg};
+  use secret_dreamscape::query::{query, GameState, QueryMsg, QueryWithPermit};
+  use secret_dreamscape::utils::permits::{
+    pubkey_to_address, Permission, Permit, PermitParams, PermitSignature, PubKey,
+  };
+
+  use crate::utils::*;
+
+  fn query_with_key(
+    deps: &Extern<MockStorage, MockApi, MockQuerier>,
+    address: &str,
+    key: &str,
+  ) -> StdResult<GameState> {
+    let query_data = query(
+      deps,
+      QueryMsg::GetGameState {
+        address: HumanAddr(address.to_string()),
+        key: key.to_string(),
+      },
+    )?;
+    from_binary(&query_data)
+  }
+
+  fn permit(pubkey: &[u8], name: &str, allowed_token: &str) -> Permit {
+    Permit {
+      params: PermitParams {
+        allowed_tokens: vec![HumanAddr(allowed_token.to_string())],
+        permit_name: name.to_string(),
+        chain_id: "secret-4".to_string(),
+        permissions: vec![Permission::Owner],
+      },
+      signature: PermitSignature {
+        pub_key: PubKey {
+          r#type: "tendermint/PubKeySecp256k1".to_string(),
+          value: Binary(pubkey.to_vec()),
+        },
+        signature: Binary(vec![0; 64]),
+      },
+    }
+  }
+
+  fn query_with_permit(
+    deps: &Extern<MockStorage, MockApi, MockQuerier>,
+    permit: Permit,
+  ) -> StdResult<GameState> {
+    let query_data = query(
+      deps,
+      QueryMsg::WithPermit {
+        permit,
+        query: QueryWithPermit::GetGameState {},
+      },
+    )?;
+    from_binary(&query_data)
+  }
+
+  /// Test if a created viewing key is returned and can be used to see the game
+  #[test]
+  fn created_viewing_key_can_query() {
+    let (_, mut deps) = init_with_2_players(false);
+
+    let response = handle(
+      &mut deps,
+      mock_env("player0", &[]),
+      HandleMsg::CreateViewingKey {
+        entropy: "some entropy".to_string(),
+      },
+    )
+    .unwrap();
+    let key = match from_binary(&response.data.unwrap()).unwrap() {
+      HandleAnswer::CreateViewingKey { key } => key,
+      _ => panic!("Viewing key wasn't returned"),
+    };
+
+    let game = query_with_key(&deps, "player0", &key).unwrap();
+    assert_eq!(game.hand.len(), 5, "Player couldn't see their hand");
+    assert!(
+      query_with_key(&deps, "player0", "key0").is_err(),
+      "Old viewing key still works"
+    );
+  }
+
+  /// Test if a wrong or someone else's viewing key is rejected
+  #[test]
+  fn wrong_viewing_key_is_rejected() {
+    let (_, deps) = init_with_2_players(false);
+
+    assert!(
+      query_with_key(&deps, "player0", "wrong key").is_err(),
+      "Wrong viewing key was accepted"
+    );
+    assert!(
+      query_with_key(&deps, "player0", "key1").is_err(),
+      "Opponent's viewing key was accepted"
+    );
+    assert!(
+      query_with_key(&deps, "player5", "").is_err(),
+      "Missing viewing key was accepted"
+    );
+  }
+
+  /// Test if a permit signed by a seated player lets them see their hand until it's revoked
+  #[test]
+  fn permit_can_query_until_revoked() {
+    let (_, mut deps) = init_with_2_players(false);
+    let pubkey = [2; 33];
+    let signer = pubkey_to_address(&pubkey);
+
+    let mut signer_env = mock_env(signer.clone(), &[]);
+    signer_env.block.time = 0;
+    handle(
+      &mut deps,
+      signer_env,
+      HandleMsg::Join {
+        nfts: vec![],
+        commitment: commitment_for(2),
+        password: "".to_string(),
+      },
+    )
+    .unwrap();
+
+    let game = query_with_permit(&deps, permit(&pubkey, "game", MOCK_CONTRACT_ADDR)).unwrap();
+    // the signer joined mid-hand, so they only see their seat until the next hand
+    assert!(
+      game.players.iter().any(|p| p.waiting),
+      "Signer couldn't see their seat"
+    );
+    assert!(game.hand.is_empty(), "Signer was dealt in mid-hand");
+
+    assert!(
+      query_with_permit(&deps, permit(&pubkey, "game", "another table")).is_err(),
+      "Permit for another contract was accepted"
+    );
+
+    handle(
+      &mut deps,
+      mock_env(signer, &[]),
+      HandleMsg::RevokePermit {
+        permit_name: "game".to_string(),
+      },
+    )
+    .unwrap();
+    assert!(
+      query_with_permit(&deps, permit(&pubkey, "game", MOCK_CONTRACT_ADDR)).is_err(),
+      "Revoked permit was accepted"
+    );
+  }
+}
@@ -1,10 +1,11 @@
 mod utils;
 
 #[cfg(test)]
+#[allow(unused_must_use)]
 mod test {
-  use cosmwasm_std::{BankMsg, Coin, CosmosMsg, HumanAddr, Uint128};
+  use cosmwasm_std::{HumanAddr, Uint128};
 
-  use secret_dreamscape::game_state::Card;
+  use secret_dreamscape::game_state::{Card, Dictionary};
   use secret_dreamscape::utils::cards::get_score_for_word;
 
   use crate::utils::*;
@@ -16,25 +17,30 @@ mod test {
       Card {
         letter: 0,
         gold: false,
+        blank: false,
       },
       Card {
         letter: 1,
         gold: false,
+        blank: false,
       },
       Card {
         letter: 2,
         gold: false,
+        blank: false,
       },
       Card {
         letter: 3,
         gold: false,
+        blank: false,
       },
       Card {
         letter: 4,
         gold: false,
+        blank: false,
       },
     ];
-    let score = get_score_for_word(word.as_slice());
+    let score = get_score_for_word(word.as_slice(), &Dictionary::default());
     assert_eq!(score, 0, "invalid word should have 0 points");
   }
 
@@ -46,45 +52,49 @@ mod test {
       Card {
         letter: 19,
         gold: false,
+        blank: false,
       },
       Card {
         letter: 4,
         gold: false,
+        blank: false,
       },
       Card {
         letter: 18,
         gold: false,
+        blank: false,
       },
       Card {
         letter: 19,
         gold: false,
+        blank: false,
       },
     ];
-    let score = get_score_for_word(word.as_slice());
+    let score = get_score_for_word(word.as_slice(), &Dictionary::default());
     assert_eq!(
       score, 4,
       "a score with no gold should be the sum of the points for each card"
     );
     word[0].gold = true;
-    let score = get_score_for_word(word.as_slice());
+    let score = get_score_for_word(word.as_slice(), &Dictionary::default());
     assert_eq!(
       score, 8,
       "a word with one gold card should double the score"
     );
     word[1].gold = true;
-    let score = get_score_for_word(word.as_slice());
+    let score = get_score_for_word(word.as_slice(), &Dictionary::default());
     assert_eq!(
       score, 16,
       "a word with two gold cards should double the score twice"
     );
     word[2].gold = true;
-    let score = get_score_for_word(word.as_slice());
+    let score = get_score_for_word(word.as_slice(), &Dictionary::default());
     assert_eq!(
       score, 32,
       "a word with two gold cards should double the score three times"
     );
     word[3].gold = true;
-    let score = get_score_for_word(word.as_slice());
+    let score = get_score_for_word(word.as_slice(), &Dictionary::default());
     assert_eq!(
       score, 64,
       "a word with two gold cards should double the score four times"
@@ -105,21 +115,21 @@ mod test {
     send_bet(&mut deps, 2, Uint128(1_000_000));
     send_bet(&mut deps, 3, Uint128(1_000_000));
 
-    // letters on player1's hands are: y, t, g, c, l
-    // letters on player2's hands are: r, t, i, a, d
-    // letters on player3's hands are: t, a, i, k, o
-    // letters on player4's hands are: a, a, r, m gold, s
-    // letters on river are: l, i, n, a, b
-    put_down_word(&mut deps, 0, vec![254, 251, 250, 4, 0, 3, 253, 252]); // billycan: 15
-    put_down_word(&mut deps, 1, vec![254, 253, 252, 4, 2, 1]); // bandit: 9
-    put_down_word(&mut deps, 2, vec![2, 252, 3, 254, 250, 4, 0]); // inkblot: 13
-    put_down_word(&mut deps, 3, vec![254, 253, 251, 250, 4, 3, 0, 252]); // bailsman: 24
+    // p0: o, l, p, o, r
+    // p1: e gold, r, a, n gold, r
+    // p2: n, t, i, a, f
+    // p3: i, p, a, a, d
+    // river: e, o, e, r, m
+    put_down_word(&mut deps, 0, vec![250, 254, 2, 252, 4, 0, 253]); // emperor: 11
+    put_down_word(&mut deps, 1, vec![1, 0, 3, 2, 254, 250]); // rename: 32
+    put_down_word(&mut deps, 2, vec![4, 251, 253, 250, 1, 2, 254, 252]); // foretime: 13
+    put_down_word(&mut deps, 3, vec![1, 253, 250, 254, 2, 4, 252]); // premade: 12
 
     let game = get_game_state(&mut deps, 0);
 
     assert_eq!(
       game.winner,
-      Some(HumanAddr("player3".to_string())),
+      Some(HumanAddr("player1".to_string())),
       "Winner is not the highest word"
     );
   }
@@ -138,39 +148,27 @@ mod test {
     send_bet(&mut deps, 2, Uint128(1_000_000));
     send_bet(&mut deps, 3, Uint128(1_000_000));
 
-    // letters on player1's hands are: y, t, g, c, l
-    // letters on player2's hands are: r, t, i, a, d
-    // letters on player3's hands are: t, a, i, k, o
-    // letters on player4's hands are: a, a, r, m gold, s
-    // letters on river are: l, i, n, a, b
-    put_down_word(&mut deps, 0, vec![254, 251, 250, 4, 0, 3, 253, 252]); // billycan: 15
-    put_down_word(&mut deps, 1, vec![254, 253, 252, 4, 2, 1]); // bandit: 9
-    put_down_word(&mut deps, 2, vec![2, 252, 3, 254, 250, 4, 0]); // inkblot: 13
-    let final_step = put_down_word(&mut deps, 3, vec![254, 253, 251, 250, 4, 3, 0, 252]); // bailsman: 24
-
-    let transfer_message = final_step.unwrap().messages[0].clone();
-    match transfer_message {
-      CosmosMsg::Bank(msg) => match msg {
-        BankMsg::Send {
-          from_address: _,
-          to_address,
-          amount,
-        } => {
-          assert_eq!(
-            to_address,
-            HumanAddr("player3".to_string()),
-            "Winner did not collect the pot"
-          );
-          assert_eq!(
-            amount[0],
-            Coin {
-              amount: Uint128(8_000_000),
-              denom: "uscrt".to_string(),
-            }
-          );
-        }
-      },
-      _ => panic!("Expected bank transfer message"),
+    // p0: o, l, p, o, r
+    // p1: e gold, r, a, n gold, r
+    // p2: n, t, i, a, f
+    // p3: i, p, a, a, d
+    // river: e, o, e, r, m
+    put_down_word(&mut deps, 0, vec![250, 254, 2, 252, 4, 0, 253]); // emperor: 11
+    put_down_word(&mut deps, 1, vec![1, 0, 3, 2, 254, 250]); // rename: 32
+    put_down_word(&mut deps, 2, vec![4, 251, 253, 250, 1, 2, 254, 252]); // foretime: 13
+    put_down_word(&mut deps, 3, vec![1, 253, 250, 254, 2, 4, 252]); // premade: 12
+
+    let game = get_game_state(&mut deps, 0);
+    // 10 SCRT bought in, 2 SCRT bet and the 8 SCRT pot minus the 10% rake won
+    assert_eq!(
+      game.players[1].chips, 15_200_000,
+      "Winner did not collect the pot"
+    );
+    for i in [0, 2, 3].iter() {
+      assert_eq!(
+        game.players[*i].chips, 8_000_000,
+        "Loser got chips back from the pot"
+      );
     }
   }
 